@@ -0,0 +1,93 @@
+//! GitHub-compatible heading anchors
+
+use std::collections::HashMap;
+
+/// Convert heading text to a GitHub-style anchor slug
+///
+/// Lowercases the text, drops punctuation other than `-` and `_`, and turns
+/// each space into `-`. Unlike GitHub, duplicate headings are not suffixed;
+/// the parser does that when assigning `ParsedSection::anchor`.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::slugify;
+///
+/// assert_eq!(slugify("Tasks / Subtasks"), "tasks--subtasks");
+/// assert_eq!(slugify("What's New in v2.0?"), "whats-new-in-v20");
+/// ```
+pub fn slugify(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Generates unique slugs, suffixing duplicates with `-1`, `-2`, ...
+#[derive(Debug, Default)]
+pub(crate) struct Slugger {
+    occurrences: HashMap<String, usize>,
+}
+
+impl Slugger {
+    /// Get a unique slug for heading text
+    pub(crate) fn slug(&mut self, text: &str) -> String {
+        let base = slugify(text);
+        let mut slug = base.clone();
+        while self.occurrences.contains_key(&slug) {
+            let count = self.occurrences.entry(base.clone()).or_default();
+            *count += 1;
+            slug = format!("{}-{}", base, count);
+        }
+        self.occurrences.insert(slug.clone(), 0);
+        slug
+    }
+
+    /// Reserve an explicit ID so generated slugs do not reuse it
+    pub(crate) fn reserve(&mut self, id: &str) {
+        self.occurrences.entry(id.to_string()).or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello World"), "hello-world");
+        assert_eq!(slugify("  API_Reference  "), "api_reference");
+        assert_eq!(slugify("C++ & Rust"), "c--rust");
+        assert_eq!(slugify("Ünïcödé Straße"), "ünïcödé-straße");
+        assert_eq!(slugify("{{variable}}"), "variable");
+    }
+
+    #[test]
+    fn test_slugger_duplicates() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Notes"), "notes");
+        assert_eq!(slugger.slug("Notes"), "notes-1");
+        assert_eq!(slugger.slug("Notes"), "notes-2");
+    }
+
+    #[test]
+    fn test_slugger_avoids_existing_suffix() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Foo 1"), "foo-1");
+        assert_eq!(slugger.slug("Foo"), "foo");
+        assert_eq!(slugger.slug("Foo"), "foo-2");
+    }
+
+    #[test]
+    fn test_slugger_reserve() {
+        let mut slugger = Slugger::default();
+        slugger.reserve("setup");
+        assert_eq!(slugger.slug("Setup"), "setup-1");
+    }
+}
@@ -0,0 +1,154 @@
+//! GitHub and Obsidian style callouts (admonitions)
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Regex for the callout marker line: `[!KIND]`, `[!KIND]- Title`, `[!KIND]+ Title`
+static CALLOUT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\[!([A-Za-z][\w-]*)\]([+-])?[ \t]*(.*)$").expect("Invalid callout regex")
+});
+
+/// Kind of callout
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CalloutKind {
+    /// `[!NOTE]`
+    Note,
+    /// `[!TIP]`
+    Tip,
+    /// `[!IMPORTANT]`
+    Important,
+    /// `[!WARNING]`
+    Warning,
+    /// `[!CAUTION]`
+    Caution,
+    /// Any other kind, lowercased (e.g. Obsidian's `[!example]`)
+    Other(String),
+}
+
+impl CalloutKind {
+    /// Parse a callout kind, ignoring case
+    pub fn parse(kind: &str) -> Self {
+        match kind.to_ascii_lowercase().as_str() {
+            "note" => CalloutKind::Note,
+            "tip" => CalloutKind::Tip,
+            "important" => CalloutKind::Important,
+            "warning" => CalloutKind::Warning,
+            "caution" => CalloutKind::Caution,
+            other => CalloutKind::Other(other.to_string()),
+        }
+    }
+
+    /// Get string representation of callout kind
+    pub fn as_str(&self) -> &str {
+        match self {
+            CalloutKind::Note => "note",
+            CalloutKind::Tip => "tip",
+            CalloutKind::Important => "important",
+            CalloutKind::Warning => "warning",
+            CalloutKind::Caution => "caution",
+            CalloutKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl std::fmt::Display for CalloutKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A blockquote written as a callout
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{CalloutKind, MarkdownParser, SectionType};
+///
+/// let doc = MarkdownParser::new()
+///     .parse("> [!WARNING]- Breaking change\n> The API moved.")
+///     .unwrap();
+///
+/// let section = &doc.sections[0];
+/// assert_eq!(section.section_type, SectionType::Blockquote);
+///
+/// let callout = section.callout.as_ref().unwrap();
+/// assert_eq!(callout.kind, CalloutKind::Warning);
+/// assert_eq!(callout.title.as_deref(), Some("Breaking change"));
+/// assert!(callout.foldable);
+/// assert!(callout.collapsed);
+/// assert_eq!(callout.content, "The API moved.");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Callout {
+    /// Callout kind
+    pub kind: CalloutKind,
+    /// Custom title after the marker
+    pub title: Option<String>,
+    /// Whether the callout can be folded (`[!KIND]-` or `[!KIND]+`)
+    pub foldable: bool,
+    /// Whether the callout starts folded (`[!KIND]-`)
+    pub collapsed: bool,
+    /// Plain-text body without the marker line
+    pub content: String,
+}
+
+impl Callout {
+    /// Parse a callout from the plain-text content of a blockquote
+    pub fn parse(content: &str) -> Option<Self> {
+        let (first, body) = content.split_once('\n').unwrap_or((content, ""));
+        let caps = CALLOUT_REGEX.captures(first.trim())?;
+        let fold = caps.get(2).map(|m| m.as_str());
+        let title = caps.get(3).map(|m| m.as_str().trim()).unwrap_or("");
+
+        Some(Self {
+            kind: CalloutKind::parse(&caps[1]),
+            title: (!title.is_empty()).then(|| title.to_string()),
+            foldable: fold.is_some(),
+            collapsed: fold == Some("-"),
+            content: body.trim().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_parse() {
+        assert_eq!(CalloutKind::parse("NOTE"), CalloutKind::Note);
+        assert_eq!(CalloutKind::parse("Caution"), CalloutKind::Caution);
+        assert_eq!(
+            CalloutKind::parse("Example"),
+            CalloutKind::Other("example".to_string())
+        );
+        assert_eq!(CalloutKind::parse("Example").as_str(), "example");
+    }
+
+    #[test]
+    fn test_parse_simple() {
+        let callout = Callout::parse("[!NOTE]\nRemember this.").unwrap();
+        assert_eq!(callout.kind, CalloutKind::Note);
+        assert_eq!(callout.title, None);
+        assert!(!callout.foldable);
+        assert_eq!(callout.content, "Remember this.");
+    }
+
+    #[test]
+    fn test_parse_foldable() {
+        let callout = Callout::parse("[!tip]+ Expanded").unwrap();
+        assert!(callout.foldable);
+        assert!(!callout.collapsed);
+        assert_eq!(callout.title.as_deref(), Some("Expanded"));
+        assert_eq!(callout.content, "");
+    }
+
+    #[test]
+    fn test_parse_rejects_plain_quotes() {
+        assert!(Callout::parse("Just a quote").is_none());
+        assert!(Callout::parse("[NOTE] not a callout").is_none());
+        assert!(Callout::parse("Text\n[!NOTE]").is_none());
+    }
+}
@@ -3,10 +3,19 @@
 use regex::Regex;
 use std::sync::LazyLock;
 
+use crate::span::{LineIndex, SourceSpan};
+
 /// Regex for matching checklist items: `- [ ]` or `- [x]`
 static CHECKLIST_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^(\s*)- \[([ xX])\] (.+)$").expect("Invalid checklist regex"));
 
+/// Regex for a task marker after a list item's bullet or number, for
+/// parsers without the task list extension
+static TASK_MARKER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:[-*+]|\d{1,9}[.)])[ \t]+\[([ xX])\](?:[ \t\r\n]|$)")
+        .expect("Invalid task marker regex")
+});
+
 /// Regex for extracting AC references: `(AC: 1, 2, 3)`
 static AC_REF_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\(AC:\s*([^)]+)\)").expect("Invalid AC reference regex"));
@@ -24,6 +33,12 @@ pub struct ChecklistItem {
     pub indent: u32,
     /// Acceptance criteria references extracted from `(AC: 1, 2, 3)` pattern
     pub ac_refs: Vec<String>,
+    /// Location of the item, from the list marker to the end of the line
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub span: SourceSpan,
+    /// Index of the section containing the item (set by `MarkdownParser`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub section_idx: Option<usize>,
 }
 
 impl ChecklistItem {
@@ -34,6 +49,8 @@ impl ChecklistItem {
             checked,
             indent,
             ac_refs: Vec::new(),
+            span: SourceSpan::default(),
+            section_idx: None,
         }
     }
 
@@ -44,6 +61,40 @@ impl ChecklistItem {
     }
 }
 
+/// A checklist item that borrows its text from the source
+///
+/// Produced by [`MarkdownParser::parse_ref`](crate::MarkdownParser::parse_ref).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ChecklistItemRef<'a> {
+    /// The text content of the checklist item (without the checkbox)
+    pub text: &'a str,
+    /// Whether the item is checked (`[x]` vs `[ ]`)
+    pub checked: bool,
+    /// Indentation level (0 = top level, 1 = nested once, etc.)
+    pub indent: u32,
+    /// Acceptance criteria references extracted from `(AC: 1, 2, 3)` pattern
+    pub ac_refs: Vec<&'a str>,
+    /// Location of the item, from the list marker to the end of the line
+    pub span: SourceSpan,
+    /// Index of the section containing the item
+    pub section_idx: Option<usize>,
+}
+
+impl ChecklistItemRef<'_> {
+    /// Copy the borrowed text into an owned `ChecklistItem`
+    pub fn into_owned(self) -> ChecklistItem {
+        ChecklistItem {
+            text: self.text.to_string(),
+            checked: self.checked,
+            indent: self.indent,
+            ac_refs: self.ac_refs.into_iter().map(String::from).collect(),
+            span: self.span,
+            section_idx: self.section_idx,
+        }
+    }
+}
+
 /// Summary of checklist completion status
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -124,9 +175,29 @@ impl Default for ChecklistSummary {
 /// assert_eq!(items[0].ac_refs, vec!["1"]);
 /// ```
 pub fn extract_checklist_items(content: &str) -> Vec<ChecklistItem> {
+    checklist_items_in(content, 0, &LineIndex::new(content))
+        .into_iter()
+        .map(ChecklistItemRef::into_owned)
+        .collect()
+}
+
+/// Extract checklist items from a slice starting at `base` in the indexed source
+pub(crate) fn checklist_items_in<'a>(
+    content: &'a str,
+    base: usize,
+    lines: &LineIndex<'_>,
+) -> Vec<ChecklistItemRef<'a>> {
     let mut items = Vec::new();
+    let mut line_start = base;
+
+    for raw_line in content.split_inclusive('\n') {
+        let line = raw_line
+            .strip_suffix('\n')
+            .map(|l| l.strip_suffix('\r').unwrap_or(l))
+            .unwrap_or(raw_line);
+        let offset = line_start;
+        line_start += raw_line.len();
 
-    for line in content.lines() {
         if let Some(caps) = CHECKLIST_REGEX.captures(line) {
             let indent_str = caps.get(1).map(|m| m.as_str()).unwrap_or("");
             let checked_char = caps.get(2).map(|m| m.as_str()).unwrap_or(" ");
@@ -139,11 +210,13 @@ pub fn extract_checklist_items(content: &str) -> Vec<ChecklistItem> {
             // Extract AC references
             let ac_refs = extract_ac_refs(text);
 
-            items.push(ChecklistItem {
-                text: text.to_string(),
+            items.push(ChecklistItemRef {
+                text,
                 checked,
                 indent,
                 ac_refs,
+                span: lines.span(offset + indent_str.len()..offset + line.len()),
+                section_idx: None,
             });
         }
     }
@@ -151,17 +224,54 @@ pub fn extract_checklist_items(content: &str) -> Vec<ChecklistItem> {
     items
 }
 
+/// Find the `[ ]` / `[x]` marker at the start of a list item's source,
+/// returning whether it is checked and the offset just past its `]`
+pub(crate) fn task_marker(item: &str) -> Option<(bool, usize)> {
+    let checkbox = TASK_MARKER_REGEX.captures(item)?.get(1)?;
+    Some((checkbox.as_str() != " ", checkbox.end() + 1))
+}
+
+/// Build the checklist item for a list item starting at `start`, whose task
+/// marker ends at `marker_end`
+///
+/// The text is the rest of the marker's line; items with no text are skipped.
+pub(crate) fn task_item<'a>(
+    source: &'a str,
+    start: usize,
+    marker_end: usize,
+    checked: bool,
+    indent: u32,
+    lines: &LineIndex<'_>,
+) -> Option<ChecklistItemRef<'a>> {
+    let rest = &source[marker_end..];
+    let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+    let text = line.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let text_end = marker_end + line.trim_end().len();
+
+    Some(ChecklistItemRef {
+        text,
+        checked,
+        indent,
+        ac_refs: extract_ac_refs(text),
+        span: lines.span(start..text_end),
+        section_idx: None,
+    })
+}
+
 /// Extract AC references from text content
 ///
 /// Parses `(AC: 1, 2, 3)` pattern and returns the individual references.
-fn extract_ac_refs(text: &str) -> Vec<String> {
+fn extract_ac_refs(text: &str) -> Vec<&str> {
     AC_REF_REGEX
         .captures(text)
         .and_then(|caps| caps.get(1))
         .map(|m| {
             m.as_str()
                 .split(',')
-                .map(|s| s.trim().to_string())
+                .map(str::trim)
                 .filter(|s| !s.is_empty())
                 .collect()
         })
@@ -286,4 +396,27 @@ mod tests {
 
         assert_eq!(items[0].ac_refs, vec!["1", "2", "3"]);
     }
+
+    #[test]
+    fn test_checklist_item_spans() {
+        let content = "# Tasks\n\n- [ ] Task 1\n  - [x] Sub\n";
+        let items = extract_checklist_items(content);
+
+        assert_eq!(items[0].span.byte_range, 9..21);
+        assert_eq!((items[0].span.start_line, items[0].span.start_col), (3, 1));
+        assert_eq!(items[1].span.byte_range, 24..33);
+        assert_eq!((items[1].span.start_line, items[1].span.start_col), (4, 3));
+        assert_eq!(&content[items[1].span.byte_range.clone()], "- [x] Sub");
+    }
+
+    #[test]
+    fn test_extract_crlf_checklist() {
+        let items = extract_checklist_items("- [ ] Task 1 (AC: 1)\r\n- [x] Task 2\r\n");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "Task 1 (AC: 1)");
+        assert_eq!(items[0].ac_refs, vec!["1"]);
+        assert_eq!(items[1].text, "Task 2");
+        assert_eq!(items[1].span.byte_range, 22..34);
+    }
 }
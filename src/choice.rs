@@ -0,0 +1,399 @@
+//! Choice groups for single and multi-select prompts
+//!
+//! A list becomes a choice group when every item starts with a radio marker,
+//! or when it follows a `<!-- choice -->` directive comment:
+//!
+//! ```markdown
+//! - ( ) Small
+//! - (x) Medium
+//! - ( ) Large
+//!
+//! <!-- choice: multi -->
+//! - [x] Email
+//! - [ ] SMS
+//! ```
+//!
+//! Radio lists are single-select and task lists under a directive are
+//! multi-select by default; `<!-- choice: single -->` and
+//! `<!-- choice: multi -->` override either.
+//!
+//! Parsing keeps every checked marker, so a single-select group written
+//! with several `(x)` options has several selections; use
+//! [`ChoiceGroup::is_conflicting`] or [`ChoiceGroup::validate`] to catch it.
+//!
+//! The `[ ]` items of a choice section are options, not tasks: they are left
+//! out of `ParsedDocument::checklist_items` and the checklist summary.
+
+use crate::error::ParseError;
+use crate::list::ParsedList;
+use crate::span::SourceSpan;
+
+/// A single option in a choice group
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChoiceOption {
+    /// Option text without the marker
+    pub text: String,
+    /// Location of the list item
+    pub span: SourceSpan,
+}
+
+/// A group of options with the current selection
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{MarkdownParser, SectionType};
+///
+/// let doc = MarkdownParser::new()
+///     .parse("- ( ) Small\n- (x) Medium\n- ( ) Large")
+///     .unwrap();
+///
+/// assert_eq!(doc.sections[0].section_type, SectionType::Choice);
+/// let mut choice = doc.sections[0].choice.clone().unwrap();
+/// assert!(!choice.multi);
+/// assert_eq!(choice.selected_texts(), vec!["Medium"]);
+///
+/// choice.select_text("Large").unwrap();
+/// assert_eq!(choice.selected, vec![2]);
+/// assert_eq!(choice.to_markdown(), "- ( ) Small\n- ( ) Medium\n- (x) Large\n");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChoiceGroup {
+    /// Options in order
+    pub options: Vec<ChoiceOption>,
+    /// Indices of the selected options, ascending
+    pub selected: Vec<usize>,
+    /// Whether more than one option may be selected
+    pub multi: bool,
+}
+
+impl ChoiceGroup {
+    /// Check if the option at `idx` is selected
+    pub fn is_selected(&self, idx: usize) -> bool {
+        self.selected.contains(&idx)
+    }
+
+    /// Check if a single-select group has more than one option selected
+    ///
+    /// This happens when the Markdown marks several options of a radio list.
+    pub fn is_conflicting(&self) -> bool {
+        !self.multi && self.selected.len() > 1
+    }
+
+    /// Check that the selection is allowed by the group
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::ChoiceError` if a single-select group has more
+    /// than one option selected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::MarkdownParser;
+    ///
+    /// let doc = MarkdownParser::new().parse("- (x) Small\n- (x) Large").unwrap();
+    /// let choice = doc.sections[0].choice.as_ref().unwrap();
+    ///
+    /// assert!(choice.is_conflicting());
+    /// assert!(choice.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), ParseError> {
+        if self.is_conflicting() {
+            return Err(ParseError::ChoiceError(format!(
+                "Single-select group has {} options selected: {:?}",
+                self.selected.len(),
+                self.selected_texts()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the text of the selected options
+    pub fn selected_texts(&self) -> Vec<&str> {
+        self.selected
+            .iter()
+            .filter_map(|&idx| self.options.get(idx))
+            .map(|o| o.text.as_str())
+            .collect()
+    }
+
+    /// Select the option at `idx`
+    ///
+    /// Single-select groups drop any previous selection.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::ChoiceError` if there is no option at `idx`.
+    pub fn select(&mut self, idx: usize) -> Result<(), ParseError> {
+        self.check_index(idx)?;
+        if !self.multi {
+            self.selected.clear();
+        }
+        if !self.selected.contains(&idx) {
+            self.selected.push(idx);
+            self.selected.sort_unstable();
+        }
+        Ok(())
+    }
+
+    /// Select the option with the given text
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::ChoiceError` if no option has that text.
+    pub fn select_text(&mut self, text: &str) -> Result<(), ParseError> {
+        let idx = self
+            .options
+            .iter()
+            .position(|o| o.text == text)
+            .ok_or_else(|| ParseError::ChoiceError(format!("No option {:?}", text)))?;
+        self.select(idx)
+    }
+
+    /// Deselect the option at `idx`
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::ChoiceError` if there is no option at `idx`.
+    pub fn deselect(&mut self, idx: usize) -> Result<(), ParseError> {
+        self.check_index(idx)?;
+        self.selected.retain(|&s| s != idx);
+        Ok(())
+    }
+
+    /// Clear the selection
+    pub fn clear(&mut self) {
+        self.selected.clear();
+    }
+
+    /// Render the group as Markdown with the current selection
+    ///
+    /// Single-select groups use `( )` markers, multi-select groups `[ ]`.
+    /// Every option becomes a `- ` bullet with its text, so the source's
+    /// list marker, ordered numbering and nested lines under an option are
+    /// not kept; use each option's `span` to edit the source in place.
+    pub fn to_markdown(&self) -> String {
+        let (open, close) = if self.multi { ('[', ']') } else { ('(', ')') };
+        self.options
+            .iter()
+            .enumerate()
+            .map(|(idx, option)| {
+                let mark = if self.is_selected(idx) { 'x' } else { ' ' };
+                format!("- {}{}{} {}\n", open, mark, close, option.text)
+            })
+            .collect()
+    }
+
+    fn check_index(&self, idx: usize) -> Result<(), ParseError> {
+        if idx < self.options.len() {
+            Ok(())
+        } else {
+            Err(ParseError::ChoiceError(format!(
+                "Option {} out of range ({} options)",
+                idx,
+                self.options.len()
+            )))
+        }
+    }
+
+    /// Build a choice group from a list
+    ///
+    /// `directive` is the value of a preceding `<!-- choice -->` comment:
+    /// `Some("")` for a bare directive, `Some("multi")` or `Some("single")`.
+    pub(crate) fn from_list(list: &ParsedList, directive: Option<&str>) -> Option<Self> {
+        if list.is_empty() {
+            return None;
+        }
+
+        let radios: Option<Vec<(bool, &str)>> =
+            list.items.iter().map(|i| radio_marker(&i.text)).collect();
+        let (markers, default_multi) = match radios {
+            Some(markers) => (markers, false),
+            None => {
+                directive?;
+                let boxes: Option<Vec<(bool, &str)>> = list
+                    .items
+                    .iter()
+                    .map(|i| match i.checked {
+                        Some(checked) => Some((checked, i.text.as_str())),
+                        None => checkbox_marker(&i.text),
+                    })
+                    .collect();
+                (boxes?, true)
+            }
+        };
+
+        let multi = match directive {
+            Some("multi") => true,
+            Some("single") => false,
+            _ => default_multi,
+        };
+        let selected = markers
+            .iter()
+            .enumerate()
+            .filter(|(_, (checked, _))| *checked)
+            .map(|(idx, _)| idx)
+            .collect();
+        let options = markers
+            .iter()
+            .zip(&list.items)
+            .map(|((_, text), item)| ChoiceOption {
+                text: text.trim().to_string(),
+                span: item.span.clone(),
+            })
+            .collect();
+
+        Some(Self {
+            options,
+            selected,
+            multi,
+        })
+    }
+}
+
+/// Split `( ) text` or `(x) text` into its state and text
+fn radio_marker(text: &str) -> Option<(bool, &str)> {
+    marker(text, '(', ')')
+}
+
+/// Split `[ ] text` or `[x] text` into its state and text
+fn checkbox_marker(text: &str) -> Option<(bool, &str)> {
+    marker(text, '[', ']')
+}
+
+fn marker(text: &str, open: char, close: char) -> Option<(bool, &str)> {
+    let mut chars = text.chars();
+    if chars.next()? != open {
+        return None;
+    }
+    let checked = match chars.next()? {
+        ' ' => false,
+        'x' | 'X' => true,
+        _ => return None,
+    };
+    if chars.next()? != close {
+        return None;
+    }
+    let rest = chars.as_str();
+    rest.starts_with(char::is_whitespace)
+        .then(|| (checked, rest.trim_start()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list::ListItem;
+
+    fn list(items: &[&str]) -> ParsedList {
+        let mut list = ParsedList::new(None, "- ");
+        list.items = items
+            .iter()
+            .map(|text| ListItem {
+                text: text.to_string(),
+                checked: None,
+                children: Vec::new(),
+                span: SourceSpan::default(),
+            })
+            .collect();
+        list
+    }
+
+    #[test]
+    fn test_marker() {
+        assert_eq!(radio_marker("( ) Small"), Some((false, "Small")));
+        assert_eq!(radio_marker("(X) Large"), Some((true, "Large")));
+        assert_eq!(radio_marker("(a) Label"), None);
+        assert_eq!(radio_marker("( )Small"), None);
+        assert_eq!(checkbox_marker("[x] Email"), Some((true, "Email")));
+    }
+
+    #[test]
+    fn test_radio_group() {
+        let group = ChoiceGroup::from_list(&list(&["( ) A", "(x) B"]), None).unwrap();
+        assert!(!group.multi);
+        assert_eq!(group.selected, vec![1]);
+        assert_eq!(group.options[0].text, "A");
+
+        assert!(ChoiceGroup::from_list(&list(&["( ) A", "B"]), None).is_none());
+    }
+
+    #[test]
+    fn test_checkbox_group_needs_directive() {
+        let items = list(&["[x] A", "[ ] B", "[x] C"]);
+        assert!(ChoiceGroup::from_list(&items, None).is_none());
+
+        let group = ChoiceGroup::from_list(&items, Some("")).unwrap();
+        assert!(group.multi);
+        assert_eq!(group.selected, vec![0, 2]);
+
+        let single = ChoiceGroup::from_list(&items, Some("single")).unwrap();
+        assert!(!single.multi);
+    }
+
+    #[test]
+    fn test_conflicting_single_select() {
+        let mut group = ChoiceGroup::from_list(&list(&["(x) A", "(x) B", "( ) C"]), None).unwrap();
+        assert_eq!(group.selected, vec![0, 1]);
+        assert!(group.is_conflicting());
+        assert!(matches!(group.validate(), Err(ParseError::ChoiceError(_))));
+
+        // Selecting again in a single-select group resolves the conflict
+        group.select(2).unwrap();
+        assert!(!group.is_conflicting());
+        assert!(group.validate().is_ok());
+
+        let multi = ChoiceGroup::from_list(&list(&["[x] A", "[x] B"]), Some("")).unwrap();
+        assert!(!multi.is_conflicting());
+        assert!(multi.validate().is_ok());
+    }
+
+    #[test]
+    fn test_select() {
+        let mut group = ChoiceGroup::from_list(&list(&["( ) A", "(x) B"]), None).unwrap();
+        group.select(0).unwrap();
+        assert_eq!(group.selected, vec![0]);
+        assert!(matches!(group.select(5), Err(ParseError::ChoiceError(_))));
+        assert!(group.select_text("C").is_err());
+
+        group.multi = true;
+        group.select(1).unwrap();
+        assert_eq!(group.selected_texts(), vec!["A", "B"]);
+        group.deselect(0).unwrap();
+        assert_eq!(group.selected, vec![1]);
+        group.clear();
+        assert!(group.selected.is_empty());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let mut group = ChoiceGroup::from_list(&list(&["[x] A", "[ ] B"]), Some("")).unwrap();
+        group.select(1).unwrap();
+        assert_eq!(group.to_markdown(), "- [x] A\n- [x] B\n");
+    }
+
+    #[test]
+    fn test_to_markdown_drops_list_formatting() {
+        let doc = crate::MarkdownParser::new()
+            .parse("1. (x) A\n   - detail\n2. ( ) B\n")
+            .unwrap();
+        let mut group = doc.sections[0].choice.clone().unwrap();
+        group.select(1).unwrap();
+
+        assert_eq!(group.to_markdown(), "- ( ) A\n- (x) B\n");
+    }
+
+    #[test]
+    fn test_choice_items_are_not_tasks() {
+        let doc = crate::MarkdownParser::new()
+            .parse("- [ ] Task\n\n<!-- choice -->\n- [x] Email\n- [ ] SMS\n")
+            .unwrap();
+
+        assert_eq!(doc.sections[2].section_type, crate::SectionType::Choice);
+        assert_eq!(doc.checklist_items.len(), 1);
+        assert_eq!(doc.checklist_items[0].text, "Task");
+        assert_eq!(doc.checklist_summary().total, 1);
+    }
+}
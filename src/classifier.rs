@@ -0,0 +1,252 @@
+//! Pluggable section classification
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::section::{ParsedSectionRef, SectionType};
+use crate::span::{LineIndex, SourceSpan};
+use crate::variables::{variable_names, variable_occurrences_in};
+
+/// Hook the parser consults for every section as it is flushed
+///
+/// Return the section to keep it, after changing its fields to retype or
+/// annotate it; return nothing to drop it; or return several sections to
+/// split it. Classifiers run in the order they were added, each on the
+/// output of the one before, and before checklist and choice detection.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{ClassifyContext, MarkdownParser, ParsedSectionRef, SectionClassifier, SectionType};
+///
+/// /// Drop paragraphs that only say "TODO" and tag the rest with their length
+/// #[derive(Debug)]
+/// struct Tidy;
+///
+/// impl SectionClassifier for Tidy {
+///     fn classify<'a>(
+///         &self,
+///         mut section: ParsedSectionRef<'a>,
+///         _ctx: &ClassifyContext<'a, '_>,
+///     ) -> Vec<ParsedSectionRef<'a>> {
+///         if section.section_type == SectionType::Paragraph && section.content == "TODO" {
+///             return Vec::new();
+///         }
+///         let length = section.content.len().to_string();
+///         section.annotations.insert("length".to_string(), length);
+///         vec![section]
+///     }
+/// }
+///
+/// let parser = MarkdownParser::builder().classifier(Tidy).build();
+/// let doc = parser.parse("# Notes\n\nTODO\n\nShip it").unwrap();
+///
+/// assert_eq!(doc.sections.len(), 2);
+/// assert_eq!(doc.sections[1].annotations["length"], "7");
+/// ```
+pub trait SectionClassifier: Send + Sync + std::fmt::Debug {
+    /// Classify one section, returning the sections to keep in its place
+    fn classify<'a>(
+        &self,
+        section: ParsedSectionRef<'a>,
+        ctx: &ClassifyContext<'a, '_>,
+    ) -> Vec<ParsedSectionRef<'a>>;
+}
+
+/// Access to the source text for classifiers that split sections
+pub struct ClassifyContext<'a, 'b> {
+    source: &'a str,
+    lines: &'b LineIndex<'a>,
+}
+
+impl<'a, 'b> ClassifyContext<'a, 'b> {
+    pub(crate) fn new(source: &'a str, lines: &'b LineIndex<'a>) -> Self {
+        Self { source, lines }
+    }
+
+    /// Full input text, which section byte ranges index into
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Location of a byte range of the source
+    pub fn span(&self, range: Range<usize>) -> SourceSpan {
+        self.lines.span(range)
+    }
+
+    /// Create a section covering a byte range of the source
+    ///
+    /// The content is the trimmed source text, so inline Markdown is kept.
+    /// Returns `None` if the range is out of bounds or does not fall on
+    /// character boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::{ClassifyContext, MarkdownParser, ParsedSectionRef, SectionClassifier, SectionType};
+    ///
+    /// /// Split paragraphs into one section per line
+    /// #[derive(Debug)]
+    /// struct Lines;
+    ///
+    /// impl SectionClassifier for Lines {
+    ///     fn classify<'a>(
+    ///         &self,
+    ///         section: ParsedSectionRef<'a>,
+    ///         ctx: &ClassifyContext<'a, '_>,
+    ///     ) -> Vec<ParsedSectionRef<'a>> {
+    ///         if section.section_type != SectionType::Paragraph {
+    ///             return vec![section];
+    ///         }
+    ///         let mut start = section.span.byte_range.start;
+    ///         section
+    ///             .raw
+    ///             .split_inclusive('\n')
+    ///             .filter_map(|line| {
+    ///                 let part = ctx.section(SectionType::Paragraph, start..start + line.len());
+    ///                 start += line.len();
+    ///                 part
+    ///             })
+    ///             .collect()
+    ///     }
+    /// }
+    ///
+    /// let parser = MarkdownParser::builder().classifier(Lines).build();
+    /// let doc = parser.parse("Status: done\nOwner: {{owner}}").unwrap();
+    ///
+    /// assert_eq!(doc.sections.len(), 2);
+    /// assert_eq!(doc.sections[1].content, "Owner: {{owner}}");
+    /// assert_eq!(doc.sections[1].span.start_line, 2);
+    /// assert_eq!(doc.variables, vec!["owner"]);
+    /// ```
+    pub fn section(
+        &self,
+        section_type: SectionType,
+        range: Range<usize>,
+    ) -> Option<ParsedSectionRef<'a>> {
+        let slice = self.source.get(range.clone())?;
+        let start = range.start + (slice.len() - slice.trim_start().len());
+        let end = range.start + slice.trim_end().len();
+        let range = start..end.max(start);
+        let raw = &self.source[range.clone()];
+
+        Some(ParsedSectionRef {
+            id: String::new(),
+            section_type,
+            level: None,
+            content: Cow::Borrowed(raw),
+            raw,
+            order_idx: 0,
+            variables: variable_names(raw).map(Cow::Borrowed).collect(),
+            span: self.lines.span(range.clone()),
+            variable_occurrences: variable_occurrences_in(raw, range.start, self.lines),
+            table: None,
+            code: None,
+            list: None,
+            choice: None,
+            callout: None,
+            anchor: None,
+            classes: Vec::new(),
+            attributes: BTreeMap::new(),
+            custom_type: None,
+            annotations: BTreeMap::new(),
+            tags: Vec::new(),
+            mentions: Vec::new(),
+            fields: BTreeMap::new(),
+        })
+    }
+}
+
+/// Gives paragraphs that start with a marker their own custom type
+///
+/// Prefixes are matched against the paragraph's Markdown source, so
+/// `**Decision:**` only matches bold text.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{MarkdownParser, PrefixClassifier, SectionType};
+///
+/// let parser = MarkdownParser::builder()
+///     .classifier(
+///         PrefixClassifier::new()
+///             .rule("**Decision:**", "decision")
+///             .rule("Status:", "status"),
+///     )
+///     .build();
+/// let doc = parser
+///     .parse("**Decision:** use Postgres\n\nStatus: accepted\n\nNotes")
+///     .unwrap();
+///
+/// assert_eq!(doc.sections[0].section_type, SectionType::Custom);
+/// assert_eq!(doc.sections[0].custom_type.as_deref(), Some("decision"));
+/// assert_eq!(doc.sections[1].custom_type.as_deref(), Some("status"));
+/// assert_eq!(doc.sections[2].section_type, SectionType::Paragraph);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PrefixClassifier {
+    /// (prefix, custom type) pairs, tried in order
+    rules: Vec<(String, String)>,
+}
+
+impl PrefixClassifier {
+    /// Create a classifier with no rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give paragraphs starting with `prefix` the custom type `name`
+    pub fn rule(mut self, prefix: impl Into<String>, name: impl Into<String>) -> Self {
+        self.rules.push((prefix.into(), name.into()));
+        self
+    }
+}
+
+impl SectionClassifier for PrefixClassifier {
+    fn classify<'a>(
+        &self,
+        mut section: ParsedSectionRef<'a>,
+        _ctx: &ClassifyContext<'a, '_>,
+    ) -> Vec<ParsedSectionRef<'a>> {
+        if section.section_type == SectionType::Paragraph {
+            if let Some((_, name)) = self
+                .rules
+                .iter()
+                .find(|(prefix, _)| section.raw.starts_with(prefix.as_str()))
+            {
+                section.section_type = SectionType::Custom;
+                section.custom_type = Some(name.clone());
+            }
+        }
+        vec![section]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_section_trims_range() {
+        let source = "  Owner: {{owner}}\n\nNext";
+        let lines = LineIndex::new(source);
+        let ctx = ClassifyContext::new(source, &lines);
+
+        let section = ctx.section(SectionType::Paragraph, 0..19).unwrap();
+        assert_eq!(section.raw, "Owner: {{owner}}");
+        assert_eq!(section.span.byte_range, 2..18);
+        assert_eq!(section.variables, vec!["owner"]);
+        assert_eq!(section.variable_occurrences[0].span.byte_range, 9..18);
+    }
+
+    #[test]
+    fn test_context_section_rejects_bad_range() {
+        let source = "Café";
+        let lines = LineIndex::new(source);
+        let ctx = ClassifyContext::new(source, &lines);
+
+        assert!(ctx.section(SectionType::Paragraph, 0..10).is_none());
+        assert!(ctx.section(SectionType::Paragraph, 0..4).is_none());
+    }
+}
@@ -0,0 +1,231 @@
+//! Code block metadata
+
+use std::collections::BTreeMap;
+
+/// How a code block was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CodeBlockKind {
+    /// Fenced with backticks or tildes
+    Fenced,
+    /// Indented by four spaces
+    Indented,
+}
+
+impl CodeBlockKind {
+    /// Get string representation of code block kind
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodeBlockKind::Fenced => "fenced",
+            CodeBlockKind::Indented => "indented",
+        }
+    }
+}
+
+impl std::fmt::Display for CodeBlockKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Metadata for a code block section
+///
+/// The info string after the opening fence is split into a language and
+/// attributes. Both `rust title="main.rs" linenos` and
+/// `rust {title="main.rs" linenos}` forms are accepted, as well as
+/// rustdoc-style `rust,ignore`. Bare words are stored as attributes with
+/// no value.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::MarkdownParser;
+///
+/// let doc = MarkdownParser::new()
+///     .parse("```rust {title=\"main.rs\" linenos}\nfn main() {}\n```")
+///     .unwrap();
+///
+/// let code = doc.sections[0].code.as_ref().unwrap();
+/// assert_eq!(code.language.as_deref(), Some("rust"));
+/// assert_eq!(code.attribute("title"), Some("main.rs"));
+/// assert!(code.has_attribute("linenos"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodeBlockInfo {
+    /// Fenced or indented
+    pub kind: CodeBlockKind,
+    /// Language from the first word of the info string
+    pub language: Option<String>,
+    /// Full info string as written after the opening fence
+    pub info: String,
+    /// Attributes parsed from the info string
+    pub attributes: BTreeMap<String, Option<String>>,
+}
+
+impl CodeBlockInfo {
+    /// Metadata for an indented code block
+    pub fn indented() -> Self {
+        Self {
+            kind: CodeBlockKind::Indented,
+            language: None,
+            info: String::new(),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Parse metadata from a fenced code block's info string
+    pub fn fenced(info: &str) -> Self {
+        let info = info.trim();
+        let mut language = None;
+        let mut attributes = BTreeMap::new();
+
+        let (head, braced) = match info.find('{') {
+            Some(idx) => (
+                &info[..idx],
+                Some(info[idx + 1..].trim_end().trim_end_matches('}')),
+            ),
+            None => (info, None),
+        };
+
+        let mut head_tokens = tokenize(head).into_iter();
+        if let Some(first) = head_tokens.next() {
+            let mut parts = first.split(',').filter(|p| !p.is_empty());
+            language = parts.next().map(String::from);
+            for flag in parts {
+                attributes.insert(flag.to_string(), None);
+            }
+        }
+        for token in head_tokens.chain(braced.map(tokenize).unwrap_or_default()) {
+            match token.split_once('=') {
+                Some((key, value)) => {
+                    attributes.insert(key.to_string(), Some(unquote(value).to_string()));
+                }
+                None => {
+                    attributes.insert(token, None);
+                }
+            }
+        }
+
+        Self {
+            kind: CodeBlockKind::Fenced,
+            language,
+            info: info.to_string(),
+            attributes,
+        }
+    }
+
+    /// Get the value of an attribute
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes.get(key)?.as_deref()
+    }
+
+    /// Check if an attribute is present (with or without a value)
+    pub fn has_attribute(&self, key: &str) -> bool {
+        self.attributes.contains_key(key)
+    }
+}
+
+impl From<pulldown_cmark::CodeBlockKind<'_>> for CodeBlockInfo {
+    fn from(kind: pulldown_cmark::CodeBlockKind<'_>) -> Self {
+        match kind {
+            pulldown_cmark::CodeBlockKind::Indented => Self::indented(),
+            pulldown_cmark::CodeBlockKind::Fenced(info) => Self::fenced(&info),
+        }
+    }
+}
+
+/// Split on whitespace, keeping double-quoted values together
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+/// Strip surrounding double quotes from an attribute value
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_only() {
+        let info = CodeBlockInfo::fenced("yaml");
+        assert_eq!(info.kind, CodeBlockKind::Fenced);
+        assert_eq!(info.language.as_deref(), Some("yaml"));
+        assert!(info.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_empty_info() {
+        let info = CodeBlockInfo::fenced("");
+        assert_eq!(info.language, None);
+        assert_eq!(info.info, "");
+    }
+
+    #[test]
+    fn test_braced_attributes() {
+        let info = CodeBlockInfo::fenced(r#"python {title="hello world.py" linenos start=5}"#);
+        assert_eq!(info.language.as_deref(), Some("python"));
+        assert_eq!(info.attribute("title"), Some("hello world.py"));
+        assert_eq!(info.attribute("start"), Some("5"));
+        assert!(info.has_attribute("linenos"));
+        assert_eq!(info.attribute("linenos"), None);
+    }
+
+    #[test]
+    fn test_bare_attributes() {
+        let info = CodeBlockInfo::fenced("mermaid theme=dark");
+        assert_eq!(info.language.as_deref(), Some("mermaid"));
+        assert_eq!(info.attribute("theme"), Some("dark"));
+    }
+
+    #[test]
+    fn test_rustdoc_flags() {
+        let info = CodeBlockInfo::fenced("rust,ignore");
+        assert_eq!(info.language.as_deref(), Some("rust"));
+        assert!(info.has_attribute("ignore"));
+    }
+
+    #[test]
+    fn test_braces_without_language() {
+        let info = CodeBlockInfo::fenced("{.sh #install}");
+        assert_eq!(info.language, None);
+        assert!(info.has_attribute(".sh"));
+        assert!(info.has_attribute("#install"));
+    }
+
+    #[test]
+    fn test_indented() {
+        let info = CodeBlockInfo::indented();
+        assert_eq!(info.kind, CodeBlockKind::Indented);
+        assert_eq!(info.kind.as_str(), "indented");
+        assert_eq!(info.language, None);
+    }
+}
@@ -1,7 +1,16 @@
 //! Document types for parsed Markdown
 
-use crate::checklist::{ChecklistItem, ChecklistSummary};
-use crate::section::ParsedSection;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::checklist::{ChecklistItem, ChecklistItemRef, ChecklistSummary};
+use crate::fields::FieldValue;
+use crate::footnote::Footnote;
+use crate::html::{HtmlComment, HtmlFragment};
+use crate::link::{LinkDefinition, ParsedLink};
+use crate::section::{ParsedSection, ParsedSectionRef};
+use crate::title::TitleSource;
+use crate::wiki::WikiLink;
 
 /// Type of edge relationship between sections
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -66,8 +75,11 @@ impl ParsedEdge {
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParsedDocument {
-    /// Document title (extracted from first H1)
+    /// Document title (from the first H1 unless other sources are configured)
     pub title: Option<String>,
+    /// Where the title came from
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub title_source: Option<TitleSource>,
     /// All sections in the document
     pub sections: Vec<ParsedSection>,
     /// All unique variable names found in the document
@@ -76,6 +88,39 @@ pub struct ParsedDocument {
     pub edges: Vec<ParsedEdge>,
     /// All checklist items found in the document
     pub checklist_items: Vec<ChecklistItem>,
+    /// All links in document order (reference links resolved)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub links: Vec<ParsedLink>,
+    /// All images in document order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub images: Vec<ParsedLink>,
+    /// Link reference definitions (`[label]: url`) in document order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub link_definitions: Vec<LinkDefinition>,
+    /// Wiki-links and embeds (`[[Page]]`, `![[Page]]`) in document order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub wiki_links: Vec<WikiLink>,
+    /// Inline `#tags` from every section merged with the frontmatter `tags` list
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: BTreeSet<String>,
+    /// `@mentions` from every section
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mentions: BTreeSet<String>,
+    /// Inline fields from every section; the first occurrence of a key wins
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fields: BTreeMap<String, FieldValue>,
+    /// Footnotes by label (when footnotes are enabled)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub footnotes: BTreeMap<String, Footnote>,
+    /// Inline HTML fragments in document order (comments excluded)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inline_html: Vec<HtmlFragment>,
+    /// HTML comments from blocks and inline positions in document order
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub html_comments: Vec<HtmlComment>,
+    /// Encoding the input was decoded from (set by `parse_bytes` and `parse_file`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub encoding: Option<String>,
     /// YAML frontmatter (when frontmatter feature is enabled)
     #[cfg(feature = "frontmatter")]
     pub frontmatter: Option<std::collections::HashMap<String, serde_yaml::Value>>,
@@ -86,10 +131,22 @@ impl ParsedDocument {
     pub fn new() -> Self {
         Self {
             title: None,
+            title_source: None,
             sections: Vec::new(),
             variables: Vec::new(),
             edges: Vec::new(),
             checklist_items: Vec::new(),
+            links: Vec::new(),
+            images: Vec::new(),
+            link_definitions: Vec::new(),
+            wiki_links: Vec::new(),
+            tags: BTreeSet::new(),
+            mentions: BTreeSet::new(),
+            fields: BTreeMap::new(),
+            footnotes: BTreeMap::new(),
+            inline_html: Vec::new(),
+            html_comments: Vec::new(),
+            encoding: None,
             #[cfg(feature = "frontmatter")]
             frontmatter: None,
         }
@@ -100,6 +157,73 @@ impl ParsedDocument {
         ChecklistSummary::from_items(&self.checklist_items)
     }
 
+    /// Get the checklist items belonging to a section
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::{MarkdownParser, SectionType};
+    ///
+    /// let doc = MarkdownParser::new()
+    ///     .parse("## Tasks\n\n- [x] Write\n- [ ] Review")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(doc.sections[1].section_type, SectionType::Checklist);
+    /// assert_eq!(doc.checklist_items[1].section_idx, Some(1));
+    /// assert_eq!(doc.checklist_items_in(1).len(), 2);
+    /// ```
+    pub fn checklist_items_in(&self, section_idx: usize) -> Vec<&ChecklistItem> {
+        self.checklist_items
+            .iter()
+            .filter(|item| item.section_idx == Some(section_idx))
+            .collect()
+    }
+
+    /// Get footnotes that are referenced but never defined
+    pub fn dangling_footnotes(&self) -> Vec<&Footnote> {
+        self.footnotes
+            .values()
+            .filter(|f| f.is_dangling())
+            .collect()
+    }
+
+    /// Get footnotes that are defined but never referenced
+    pub fn unused_footnotes(&self) -> Vec<&Footnote> {
+        self.footnotes.values().filter(|f| f.is_unused()).collect()
+    }
+
+    /// Get the value of the first `<!-- key: value -->` comment annotation
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        self.html_comments
+            .iter()
+            .filter_map(HtmlComment::annotation)
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Get the heading section with the given anchor
+    ///
+    /// A leading `#` is ignored, so URL fragments can be passed directly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::MarkdownParser;
+    ///
+    /// let doc = MarkdownParser::new()
+    ///     .parse("# Story\n\n## Tasks / Subtasks\n\n## Notes\n\n## Notes")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(doc.sections[1].anchor.as_deref(), Some("tasks--subtasks"));
+    /// assert_eq!(doc.section_by_anchor("#notes-1").unwrap().order_idx, 3);
+    /// ```
+    pub fn section_by_anchor(&self, anchor: &str) -> Option<&ParsedSection> {
+        let anchor = anchor.strip_prefix('#').unwrap_or(anchor);
+        self.sections
+            .iter()
+            .find(|s| s.anchor.as_deref() == Some(anchor))
+    }
+
     /// Get section by index
     pub fn get_section(&self, idx: usize) -> Option<&ParsedSection> {
         self.sections.get(idx)
@@ -110,6 +234,86 @@ impl ParsedDocument {
         self.sections.iter().find(|s| s.id == id)
     }
 
+    /// Get the index of the heading that contains a section
+    ///
+    /// Returns `None` for sections that appear before any heading and for
+    /// top-level headings.
+    pub fn parent_of(&self, idx: usize) -> Option<usize> {
+        self.edges
+            .iter()
+            .find(|e| e.edge_type == EdgeType::Contains && e.target_idx == idx)
+            .map(|e| e.source_idx)
+    }
+
+    /// Get the indices of the sections directly contained by a heading
+    pub fn children_of(&self, idx: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Contains && e.source_idx == idx)
+            .map(|e| e.target_idx)
+            .collect()
+    }
+
+    /// Get the chain of heading texts leading to a section
+    ///
+    /// The path runs from the outermost heading down to the section's
+    /// parent, and includes the section itself when it is a heading.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::MarkdownParser;
+    ///
+    /// let doc = MarkdownParser::new()
+    ///     .parse("# Story\n\n## Tasks\n\n- [ ] Write tests")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(doc.heading_path(2), vec!["Story", "Tasks"]);
+    /// ```
+    pub fn heading_path(&self, idx: usize) -> Vec<&str> {
+        let mut path = Vec::new();
+        let mut current = match self.sections.get(idx) {
+            Some(section) if section.section_type == crate::section::SectionType::Heading => {
+                Some(idx)
+            }
+            Some(_) => self.parent_of(idx),
+            None => None,
+        };
+        while let Some(heading_idx) = current {
+            path.push(self.sections[heading_idx].content.as_str());
+            current = self.parent_of(heading_idx);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Get all code sections in a language (case-insensitive)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::MarkdownParser;
+    ///
+    /// let doc = MarkdownParser::new()
+    ///     .parse("```mermaid\ngraph TD\n```\n\n```rust\nfn main() {}\n```")
+    ///     .unwrap();
+    ///
+    /// let diagrams = doc.code_blocks("mermaid");
+    /// assert_eq!(diagrams.len(), 1);
+    /// assert_eq!(diagrams[0].content, "graph TD");
+    /// ```
+    pub fn code_blocks(&self, language: &str) -> Vec<&ParsedSection> {
+        self.sections
+            .iter()
+            .filter(|s| {
+                s.code
+                    .as_ref()
+                    .and_then(|c| c.language.as_deref())
+                    .is_some_and(|l| l.eq_ignore_ascii_case(language))
+            })
+            .collect()
+    }
+
     /// Get all sections of a specific type
     pub fn sections_by_type(
         &self,
@@ -120,6 +324,25 @@ impl ParsedDocument {
             .filter(|s| s.section_type == section_type)
             .collect()
     }
+
+    /// Get a string frontmatter value, trimmed
+    pub(crate) fn frontmatter_string(&self, key: &str) -> Option<String> {
+        #[cfg(feature = "frontmatter")]
+        return frontmatter_string(self.frontmatter.as_ref(), key);
+        #[cfg(not(feature = "frontmatter"))]
+        {
+            let _ = key;
+            None
+        }
+    }
+
+    /// Get the frontmatter `tags` list
+    pub(crate) fn frontmatter_tags(&self) -> Vec<String> {
+        #[cfg(feature = "frontmatter")]
+        return crate::tags::frontmatter_tags(self.frontmatter.as_ref());
+        #[cfg(not(feature = "frontmatter"))]
+        Vec::new()
+    }
 }
 
 impl Default for ParsedDocument {
@@ -128,6 +351,137 @@ impl Default for ParsedDocument {
     }
 }
 
+/// A parsed Markdown document that borrows its text from the source
+///
+/// Returned by [`MarkdownParser::parse_ref`](crate::MarkdownParser::parse_ref).
+/// Section text, variable names and checklist text are slices of the input
+/// where possible, so parsing many documents allocates far less. Call
+/// [`into_owned`](Self::into_owned) to get a `ParsedDocument` that outlives
+/// the input.
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+/// use md_parser::MarkdownParser;
+///
+/// let source = String::from("# Hello {{name}}\n\n- [ ] Reply");
+/// let doc = MarkdownParser::new().parse_ref(&source).unwrap();
+///
+/// assert!(matches!(doc.sections[0].content, Cow::Borrowed("Hello {{name}}")));
+/// assert_eq!(doc.variables, vec!["name"]);
+/// assert_eq!(doc.checklist_items[0].text, "Reply");
+///
+/// let owned = doc.into_owned();
+/// drop(source);
+/// assert_eq!(owned.title.as_deref(), Some("Hello {{name}}"));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParsedDocumentRef<'a> {
+    /// Document title (from the first H1 unless other sources are configured)
+    pub title: Option<String>,
+    /// Where the title came from
+    pub title_source: Option<TitleSource>,
+    /// All sections in the document
+    pub sections: Vec<ParsedSectionRef<'a>>,
+    /// All unique variable names found in the document
+    pub variables: Vec<Cow<'a, str>>,
+    /// Edges representing relationships between sections
+    pub edges: Vec<ParsedEdge>,
+    /// All checklist items found in the document
+    pub checklist_items: Vec<ChecklistItemRef<'a>>,
+    /// All links in document order (reference links resolved)
+    pub links: Vec<ParsedLink>,
+    /// All images in document order
+    pub images: Vec<ParsedLink>,
+    /// Link reference definitions (`[label]: url`) in document order
+    pub link_definitions: Vec<LinkDefinition>,
+    /// Wiki-links and embeds (`[[Page]]`, `![[Page]]`) in document order
+    pub wiki_links: Vec<WikiLink>,
+    /// Inline `#tags` from every section merged with the frontmatter `tags` list
+    pub tags: BTreeSet<String>,
+    /// `@mentions` from every section
+    pub mentions: BTreeSet<String>,
+    /// Inline fields from every section; the first occurrence of a key wins
+    pub fields: BTreeMap<String, FieldValue>,
+    /// Footnotes by label (when footnotes are enabled)
+    pub footnotes: BTreeMap<String, Footnote>,
+    /// Inline HTML fragments in document order (comments excluded)
+    pub inline_html: Vec<HtmlFragment>,
+    /// HTML comments from blocks and inline positions in document order
+    pub html_comments: Vec<HtmlComment>,
+    /// YAML frontmatter (when frontmatter feature is enabled)
+    #[cfg(feature = "frontmatter")]
+    pub frontmatter: Option<std::collections::HashMap<String, serde_yaml::Value>>,
+}
+
+impl ParsedDocumentRef<'_> {
+    /// Copy the borrowed text into an owned `ParsedDocument`
+    pub fn into_owned(self) -> ParsedDocument {
+        ParsedDocument {
+            title: self.title,
+            title_source: self.title_source,
+            sections: self
+                .sections
+                .into_iter()
+                .map(ParsedSectionRef::into_owned)
+                .collect(),
+            variables: self.variables.into_iter().map(Cow::into_owned).collect(),
+            edges: self.edges,
+            checklist_items: self
+                .checklist_items
+                .into_iter()
+                .map(ChecklistItemRef::into_owned)
+                .collect(),
+            links: self.links,
+            images: self.images,
+            link_definitions: self.link_definitions,
+            wiki_links: self.wiki_links,
+            tags: self.tags,
+            mentions: self.mentions,
+            fields: self.fields,
+            footnotes: self.footnotes,
+            inline_html: self.inline_html,
+            html_comments: self.html_comments,
+            encoding: None,
+            #[cfg(feature = "frontmatter")]
+            frontmatter: self.frontmatter,
+        }
+    }
+
+    /// Get a string frontmatter value, trimmed
+    pub(crate) fn frontmatter_string(&self, key: &str) -> Option<String> {
+        #[cfg(feature = "frontmatter")]
+        return frontmatter_string(self.frontmatter.as_ref(), key);
+        #[cfg(not(feature = "frontmatter"))]
+        {
+            let _ = key;
+            None
+        }
+    }
+
+    /// Get the frontmatter `tags` list
+    pub(crate) fn frontmatter_tags(&self) -> Vec<String> {
+        #[cfg(feature = "frontmatter")]
+        return crate::tags::frontmatter_tags(self.frontmatter.as_ref());
+        #[cfg(not(feature = "frontmatter"))]
+        Vec::new()
+    }
+}
+
+/// Look up a string frontmatter value, trimmed
+#[cfg(feature = "frontmatter")]
+fn frontmatter_string(
+    frontmatter: Option<&std::collections::HashMap<String, serde_yaml::Value>>,
+    key: &str,
+) -> Option<String> {
+    frontmatter?
+        .get(key)?
+        .as_str()
+        .map(|value| value.trim().to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,18 +517,10 @@ mod tests {
     #[test]
     fn test_parsed_document_checklist_summary() {
         let mut doc = ParsedDocument::new();
-        doc.checklist_items.push(ChecklistItem {
-            text: "Task 1".to_string(),
-            checked: true,
-            indent: 0,
-            ac_refs: vec![],
-        });
-        doc.checklist_items.push(ChecklistItem {
-            text: "Task 2".to_string(),
-            checked: false,
-            indent: 0,
-            ac_refs: vec![],
-        });
+        doc.checklist_items
+            .push(ChecklistItem::new("Task 1".to_string(), true, 0));
+        doc.checklist_items
+            .push(ChecklistItem::new("Task 2".to_string(), false, 0));
 
         let summary = doc.checklist_summary();
         assert_eq!(summary.total, 2);
@@ -204,4 +550,65 @@ mod tests {
         let headings = doc.sections_by_type(SectionType::Heading);
         assert_eq!(headings.len(), 2);
     }
+
+    fn hierarchy_doc() -> ParsedDocument {
+        let mut doc = ParsedDocument::new();
+        for (i, (section_type, content)) in [
+            (SectionType::Heading, "Story"),
+            (SectionType::Heading, "Tasks"),
+            (SectionType::List, "Write tests"),
+            (SectionType::Heading, "Notes"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            doc.sections.push(crate::section::ParsedSection::new(
+                section_type,
+                content.to_string(),
+                i as u32,
+            ));
+        }
+        doc.edges.push(ParsedEdge::contains(0, 1));
+        doc.edges.push(ParsedEdge::contains(1, 2));
+        doc.edges.push(ParsedEdge::contains(0, 3));
+        doc
+    }
+
+    #[test]
+    fn test_parent_and_children() {
+        let doc = hierarchy_doc();
+
+        assert_eq!(doc.parent_of(0), None);
+        assert_eq!(doc.parent_of(2), Some(1));
+        assert_eq!(doc.children_of(0), vec![1, 3]);
+        assert!(doc.children_of(2).is_empty());
+    }
+
+    #[test]
+    fn test_heading_path() {
+        let doc = hierarchy_doc();
+
+        assert_eq!(doc.heading_path(0), vec!["Story"]);
+        assert_eq!(doc.heading_path(2), vec!["Story", "Tasks"]);
+        assert_eq!(doc.heading_path(3), vec!["Story", "Notes"]);
+        assert!(doc.heading_path(99).is_empty());
+    }
+
+    #[test]
+    fn test_code_blocks_by_language() {
+        let mut doc = ParsedDocument::new();
+        for (i, info) in ["rust", "YAML", "rust,ignore"].into_iter().enumerate() {
+            let mut section = crate::section::ParsedSection::new(
+                SectionType::Code,
+                format!("block {}", i),
+                i as u32,
+            );
+            section.code = Some(crate::code::CodeBlockInfo::fenced(info));
+            doc.sections.push(section);
+        }
+
+        assert_eq!(doc.code_blocks("rust").len(), 2);
+        assert_eq!(doc.code_blocks("yaml").len(), 1);
+        assert!(doc.code_blocks("python").is_empty());
+    }
 }
@@ -0,0 +1,127 @@
+//! Decoding and normalizing raw Markdown bytes
+
+use std::borrow::Cow;
+
+use crate::error::ParseError;
+
+/// Decode Markdown bytes to text, returning the name of the encoding used
+///
+/// A UTF-8 or UTF-16 byte order mark selects the encoding. A UTF-8 BOM is
+/// kept, so byte offsets in the text match the input; the parser skips it.
+/// Other input must be UTF-8; with the `encoding` feature, input that is not
+/// valid UTF-8 is decoded with the encoding named by the `fallback` label
+/// (Windows-1252, a superset of Latin-1, if `None`) instead.
+#[cfg_attr(not(feature = "encoding"), allow(unused_variables))]
+pub(crate) fn decode<'a>(
+    bytes: &'a [u8],
+    fallback: Option<&str>,
+) -> Result<(Cow<'a, str>, &'static str), ParseError> {
+    #[cfg(feature = "encoding")]
+    let fallback = match fallback {
+        Some(label) => encoding_rs::Encoding::for_label(label.as_bytes()).ok_or_else(|| {
+            ParseError::InvalidEncoding(format!("unknown encoding label {:?}", label))
+        })?,
+        None => encoding_rs::WINDOWS_1252,
+    };
+
+    if bytes.starts_with(b"\xEF\xBB\xBF") {
+        return utf8(bytes).map(|text| (text, "UTF-8"));
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+        return utf16(rest, u16::from_le_bytes).map(|text| (Cow::Owned(text), "UTF-16LE"));
+    }
+    if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+        return utf16(rest, u16::from_be_bytes).map(|text| (Cow::Owned(text), "UTF-16BE"));
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok((Cow::Borrowed(text), "UTF-8")),
+        #[cfg(feature = "encoding")]
+        Err(_) => {
+            let (text, _) = fallback.decode_without_bom_handling(bytes);
+            Ok((text, fallback.name()))
+        }
+        #[cfg(not(feature = "encoding"))]
+        Err(e) => Err(ParseError::InvalidEncoding(format!(
+            "input is not UTF-8 ({}); enable the `encoding` feature to decode legacy encodings",
+            e
+        ))),
+    }
+}
+
+/// Decode UTF-8 bytes, reporting where they stop being valid
+fn utf8(bytes: &[u8]) -> Result<Cow<'_, str>, ParseError> {
+    std::str::from_utf8(bytes)
+        .map(Cow::Borrowed)
+        .map_err(|e| ParseError::InvalidEncoding(format!("input is not UTF-8 ({})", e)))
+}
+
+/// Decode UTF-16 bytes with the given byte order
+fn utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Result<String, ParseError> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(ParseError::InvalidEncoding(
+            "UTF-16 input has an odd number of bytes".to_string(),
+        ));
+    }
+    let units = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|e| ParseError::InvalidEncoding(format!("input is not UTF-16 ({})", e)))
+}
+
+/// Convert `\r\n` and lone `\r` line endings to `\n`
+pub(crate) fn normalize_line_endings(text: &str) -> Cow<'_, str> {
+    if !text.contains('\r') {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_boms() {
+        let (text, encoding) = decode(b"\xEF\xBB\xBF# Hi", None).unwrap();
+        assert_eq!((text.as_ref(), encoding), ("\u{feff}# Hi", "UTF-8"));
+
+        let (text, encoding) = decode(b"\xFF\xFE#\x00 \x00H\x00i\x00", None).unwrap();
+        assert_eq!((text.as_ref(), encoding), ("# Hi", "UTF-16LE"));
+
+        let (text, encoding) = decode(b"\xFE\xFF\x00#\x00 \x00H\x00i", None).unwrap();
+        assert_eq!((text.as_ref(), encoding), ("# Hi", "UTF-16BE"));
+
+        assert!(decode(b"\xFF\xFE#", None).is_err());
+    }
+
+    #[test]
+    fn test_decode_legacy() {
+        let result = decode(b"Caf\xE9", None);
+        #[cfg(feature = "encoding")]
+        assert_eq!(result.unwrap(), (Cow::Borrowed("Café"), "windows-1252"));
+        #[cfg(not(feature = "encoding"))]
+        assert!(matches!(result, Err(ParseError::InvalidEncoding(_))));
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn test_decode_fallback_label() {
+        let (text, encoding) = decode(b"\xCF\xE8\xF1\xFC\xEC\xEE", Some("cp1251")).unwrap();
+        assert_eq!((text.as_ref(), encoding), ("Письмо", "windows-1251"));
+
+        // UTF-8 input ignores the fallback, but the label must be known
+        let (text, _) = decode("Письмо".as_bytes(), Some("latin2")).unwrap();
+        assert_eq!(text, "Письмо");
+        assert!(matches!(
+            decode(b"abc", Some("no-such-encoding")),
+            Err(ParseError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        assert_eq!(normalize_line_endings("a\r\nb\rc\n"), "a\nb\nc\n");
+        assert!(matches!(normalize_line_endings("a\nb"), Cow::Borrowed(_)));
+    }
+}
@@ -14,6 +14,26 @@ pub enum ParseError {
     #[error("Frontmatter parse error: {0}")]
     FrontmatterError(String),
 
+    /// Table rows could not be deserialized (returned with the serde feature)
+    #[error("Table deserialization error: {0}")]
+    TableError(String),
+
+    /// Choice selection refers to an option that does not exist
+    #[error("Choice error: {0}")]
+    ChoiceError(String),
+
+    /// Edit range is out of bounds or not on a character boundary
+    #[error("Invalid edit: {0}")]
+    InvalidEdit(String),
+
+    /// Input bytes are not valid in the detected encoding
+    #[error("Invalid encoding: {0}")]
+    InvalidEncoding(String),
+
+    /// Glob pattern passed to `parse_dir` is malformed
+    #[error("Invalid glob pattern: {0}")]
+    InvalidPattern(String),
+
     /// IO error when reading files
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
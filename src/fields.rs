@@ -0,0 +1,303 @@
+//! Inline fields (`Key:: value`, `[key:: value]` and `**Key:** value`)
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Regex for a field filling a whole line, after any list marker:
+/// Dataview `Key:: value` or bold `**Key:** value` / `**Key**: value`
+///
+/// A multi-word key followed by another `::` is prose mentioning a path
+/// such as `std::mem::take`, and is rejected in [`fields_in`].
+static LINE_FIELD_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:[-*+]|\d+[.)])?[ \t]*(?:(\w[\w \-/]*?)::[ \t]*(.*)|\*\*([^*\n]+?)(?::\*\*|\*\*:)[ \t]*(.*))$",
+    )
+    .expect("Invalid inline field regex")
+});
+
+/// Regex for Dataview fields inside a line: `[key:: value]` or `(key:: value)`
+static BRACKET_FIELD_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[\[(](\w[\w \-/]*?)::[ \t]*([^\[\]()\n]*)[\])]")
+        .expect("Invalid inline field regex")
+});
+
+/// Regex for ISO 8601 dates (`2024-05-01`)
+static DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\d{4})-(\d{2})-(\d{2})$").expect("Invalid date regex"));
+
+/// Regex for decimal numbers (`42`, `-1.5`, `3e8`)
+static NUMBER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[+-]?(?:\d+\.?\d*|\.\d+)(?:[eE][+-]?\d+)?$").expect("Invalid number regex")
+});
+
+/// Value of an inline field
+///
+/// Values are `Text` unless the parser is built with
+/// [`coerce_fields`](crate::MarkdownParserBuilder::coerce_fields), which
+/// turns numbers, `true`/`false`, `YYYY-MM-DD` dates and `[a, b]` lists
+/// into the other variants.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{FieldValue, MarkdownParser};
+///
+/// let content = "**Status:** Draft\n\npriority:: 2\ntags:: [api, v2]\n\nDue [due:: 2024-05-01]";
+///
+/// let doc = MarkdownParser::new().parse(content).unwrap();
+/// assert_eq!(doc.fields["priority"], FieldValue::Text("2".to_string()));
+///
+/// let doc = MarkdownParser::builder().coerce_fields(true).build().parse(content).unwrap();
+/// assert_eq!(doc.fields["Status"].as_str(), Some("Draft"));
+/// assert_eq!(doc.fields["priority"].as_f64(), Some(2.0));
+/// assert_eq!(doc.fields["due"], FieldValue::Date("2024-05-01".to_string()));
+/// assert_eq!(doc.fields["tags"].as_list().map(<[_]>::len), Some(2));
+/// assert_eq!(doc.sections[1].fields.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FieldValue {
+    /// Text as written, including any inline Markdown
+    Text(String),
+    /// Decimal number
+    Number(f64),
+    /// `true` or `false` (case-insensitive)
+    Bool(bool),
+    /// ISO 8601 date (`YYYY-MM-DD`), as written
+    Date(String),
+    /// `[a, b]` list, with each item coerced
+    List(Vec<FieldValue>),
+}
+
+impl FieldValue {
+    /// Parse a field value, coercing it to a typed value if `coerce` is set
+    pub(crate) fn parse(text: &str, coerce: bool) -> Self {
+        let text = text.trim();
+        if !coerce {
+            return FieldValue::Text(text.to_string());
+        }
+        if text.eq_ignore_ascii_case("true") || text.eq_ignore_ascii_case("false") {
+            return FieldValue::Bool(text.eq_ignore_ascii_case("true"));
+        }
+        if NUMBER_REGEX.is_match(text) {
+            if let Ok(number) = text.parse() {
+                return FieldValue::Number(number);
+            }
+        }
+        if is_date(text) {
+            return FieldValue::Date(text.to_string());
+        }
+        // `[[Page]]` is a wiki-link, not a list
+        if let Some(items) = text
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .filter(|items| !items.starts_with('[') && !items.contains(']'))
+        {
+            return FieldValue::List(
+                items
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| FieldValue::parse(item, true))
+                    .collect(),
+            );
+        }
+        FieldValue::Text(text.to_string())
+    }
+
+    /// The text of a `Text` or `Date` value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            FieldValue::Text(text) | FieldValue::Date(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The number of a `Number` value
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FieldValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// The flag of a `Bool` value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            FieldValue::Bool(flag) => Some(*flag),
+            _ => None,
+        }
+    }
+
+    /// The items of a `List` value
+    pub fn as_list(&self) -> Option<&[FieldValue]> {
+        match self {
+            FieldValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Check for a `YYYY-MM-DD` date with a valid month and day
+fn is_date(text: &str) -> bool {
+    let Some(caps) = DATE_REGEX.captures(text) else {
+        return false;
+    };
+    let (month, day): (u8, u8) = (caps[2].parse().unwrap_or(0), caps[3].parse().unwrap_or(0));
+    (1..=12).contains(&month) && (1..=31).contains(&day)
+}
+
+/// Find the inline fields in a section's raw text
+///
+/// `base` is the byte offset of `raw` in the source; fields whose key
+/// overlaps a `skip` range (code spans, nested code blocks and inline HTML)
+/// are ignored. Fields without a value, such as `**Acceptance Criteria:**`
+/// labels, are skipped, and the first occurrence of a key wins.
+///
+/// A line such as `Call std::mem::take here` would read as the key
+/// `Call std`, so a Dataview key containing spaces is rejected when its
+/// value holds another `::`. Single-word keys are kept, which leaves one
+/// false positive: a line starting with a path, such as
+/// `std::mem::take moves it`, is read as the field `std`.
+pub(crate) fn fields_in(
+    raw: &str,
+    base: usize,
+    skip: &[Range<usize>],
+    coerce: bool,
+) -> BTreeMap<String, FieldValue> {
+    let mut fields = BTreeMap::new();
+    let skipped = |range: Range<usize>| {
+        let range = base + range.start..base + range.end;
+        skip.iter()
+            .any(|s| s.start < range.end && range.start < s.end)
+    };
+
+    let mut line_start = 0;
+    for line in raw.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let line_range = line_start..line_start + text.len();
+        line_start += line.len();
+
+        let trimmed = text.trim_start();
+        let line_field = LINE_FIELD_REGEX.captures(trimmed).filter(|caps| {
+            // `Call std::mem::take here` mentions a path
+            caps.get(1)
+                .is_none_or(|key| !key.as_str().trim().contains(' ') || !caps[2].contains("::"))
+        });
+        if let Some(caps) = line_field {
+            let key = caps.get(1).or_else(|| caps.get(3));
+            let value = caps.get(2).or_else(|| caps.get(4));
+            if let (Some(key), Some(value)) = (key, value) {
+                let indent = line_range.start + text.len() - trimmed.len();
+                if !skipped(indent + key.start()..indent + value.start())
+                    && !value.as_str().trim().is_empty()
+                {
+                    fields
+                        .entry(key.as_str().trim().to_string())
+                        .or_insert_with(|| FieldValue::parse(value.as_str(), coerce));
+                }
+                continue;
+            }
+        }
+
+        for caps in BRACKET_FIELD_REGEX.captures_iter(text) {
+            let (Some(full), Some(value)) = (caps.get(0), caps.get(2)) else {
+                continue;
+            };
+            let key_range = line_range.start + full.start()..line_range.start + value.start();
+            if skipped(key_range) || value.as_str().trim().is_empty() {
+                continue;
+            }
+            fields
+                .entry(caps[1].trim().to_string())
+                .or_insert_with(|| FieldValue::parse(&caps[2], coerce));
+        }
+    }
+
+    fields
+}
+
+/// Merge section fields into one map, keeping the first value of each key
+pub(crate) fn merge_fields<'a>(
+    sections: impl IntoIterator<Item = &'a BTreeMap<String, FieldValue>>,
+) -> BTreeMap<String, FieldValue> {
+    let mut merged = BTreeMap::new();
+    for fields in sections {
+        for (key, value) in fields {
+            merged.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_in() {
+        let raw = "**Status:** Draft\n- **Owner**: @alice\nPriority Level:: 2\n\
+                   Labels:\n**Acceptance Criteria:**\nDue [due:: 2024-05-01] (est:: 3) and [empty:: ]";
+        let fields = fields_in(raw, 0, &[], false);
+
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields["Status"], FieldValue::Text("Draft".to_string()));
+        assert_eq!(fields["Owner"].as_str(), Some("@alice"));
+        assert_eq!(fields["Priority Level"].as_str(), Some("2"));
+        assert_eq!(fields["due"].as_str(), Some("2024-05-01"));
+        assert_eq!(fields["est"].as_str(), Some("3"));
+    }
+
+    #[test]
+    fn test_paths_are_not_fields() {
+        let raw = "Call std::mem::take here\nUse:: std::mem::take\nSee also:: the docs";
+        let fields = fields_in(raw, 0, &[], false);
+
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["See also", "Use"]);
+        assert_eq!(fields["Use"].as_str(), Some("std::mem::take"));
+
+        // The documented false positive: a line starting with a path
+        let fields = fields_in("std::mem::take moves it", 0, &[], false);
+        assert_eq!(fields["std"].as_str(), Some("mem::take moves it"));
+    }
+
+    #[test]
+    fn test_fields_skip_code() {
+        // `[a:: 1]` is a code span at 4..13; a code span in a value is kept
+        let raw = "See `[a:: 1]` and [b:: 2]\nc:: `code`";
+        let code_spans = [4..13, 30..36];
+        let fields = fields_in(raw, 0, &code_spans, false);
+
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["b", "c"]);
+        assert_eq!(fields["c"].as_str(), Some("`code`"));
+    }
+
+    #[test]
+    fn test_coerce_values() {
+        let parse = |text| FieldValue::parse(text, true);
+
+        assert_eq!(parse("42"), FieldValue::Number(42.0));
+        assert_eq!(parse("-1.5e2"), FieldValue::Number(-150.0));
+        assert_eq!(parse("TRUE"), FieldValue::Bool(true));
+        assert_eq!(
+            parse("2024-02-29"),
+            FieldValue::Date("2024-02-29".to_string())
+        );
+        assert_eq!(parse("2024-13-01").as_str(), Some("2024-13-01"));
+        assert_eq!(
+            parse("[a, 2, false]"),
+            FieldValue::List(vec![
+                FieldValue::Text("a".to_string()),
+                FieldValue::Number(2.0),
+                FieldValue::Bool(false),
+            ])
+        );
+        assert_eq!(parse("[[Page]]").as_str(), Some("[[Page]]"));
+        assert_eq!(parse("inf").as_str(), Some("inf"));
+        assert_eq!(FieldValue::parse("42", false).as_str(), Some("42"));
+    }
+}
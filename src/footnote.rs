@@ -0,0 +1,242 @@
+//! Footnote definitions and references
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::section::{SectionType, SectionView};
+use crate::span::{LineIndex, SourceSpan};
+
+/// Regex for footnote reference syntax: `[^label]`
+static FOOTNOTE_REF_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[\^([^\]\s]+)\]").expect("Invalid footnote regex"));
+
+/// A single reference to a footnote
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteReference {
+    /// Index of the section containing the reference
+    pub section_idx: Option<usize>,
+    /// Location of the `[^label]` syntax
+    pub span: SourceSpan,
+}
+
+/// A footnote with its definition and every reference to it
+///
+/// # Example
+///
+/// ```
+/// use md_parser::MarkdownParser;
+///
+/// let parser = MarkdownParser::builder().footnotes(true).build();
+/// let doc = parser
+///     .parse("Claim[^1] and [^2].\n\n[^1]: Source.\n\n[^3]: Unused.")
+///     .unwrap();
+///
+/// let note = &doc.footnotes["1"];
+/// assert_eq!(note.content.as_deref(), Some("Source."));
+/// assert_eq!(note.referencing_sections(), vec![0]);
+///
+/// assert_eq!(doc.dangling_footnotes()[0].label, "2");
+/// assert_eq!(doc.unused_footnotes()[0].label, "3");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Footnote {
+    /// Footnote label (as written in the definition, if any)
+    pub label: String,
+    /// Plain-text content of the definition, `None` if undefined
+    pub content: Option<String>,
+    /// Index of the footnote definition section
+    pub definition_idx: Option<usize>,
+    /// Every reference to this footnote in document order
+    pub references: Vec<FootnoteReference>,
+}
+
+impl Footnote {
+    /// Create a footnote with no definition or references
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            content: None,
+            definition_idx: None,
+            references: Vec::new(),
+        }
+    }
+
+    /// Check if the footnote is referenced but never defined
+    pub fn is_dangling(&self) -> bool {
+        self.definition_idx.is_none()
+    }
+
+    /// Check if the footnote is defined but never referenced
+    pub fn is_unused(&self) -> bool {
+        self.references.is_empty()
+    }
+
+    /// Get the unique indices of sections that reference this footnote
+    pub fn referencing_sections(&self) -> Vec<usize> {
+        let mut sections: Vec<usize> = self
+            .references
+            .iter()
+            .filter_map(|r| r.section_idx)
+            .collect();
+        sections.dedup();
+        sections
+    }
+}
+
+/// Assemble footnotes from definitions, resolved references and any
+/// unresolved `[^label]` text left in the sections
+///
+/// Each definition is `(label, section index, content)`; unresolved text
+/// overlapping a `skip` range is ignored.
+pub(crate) fn build_footnotes<S: SectionView>(
+    sections: &[S],
+    definitions: &[(String, usize, String)],
+    mut references: Vec<(String, FootnoteReference)>,
+    skip: &[Range<usize>],
+    lines: &LineIndex<'_>,
+) -> BTreeMap<String, Footnote> {
+    let mut footnotes: BTreeMap<String, Footnote> = BTreeMap::new();
+
+    for (label, idx, content) in definitions {
+        let footnote = footnotes
+            .entry(label.clone())
+            .or_insert_with(|| Footnote::new(label.clone()));
+        footnote.content = Some(content.clone());
+        footnote.definition_idx = Some(*idx);
+    }
+
+    references.extend(unresolved_references(sections, &references, skip, lines));
+    references.sort_by_key(|(_, r)| r.span.byte_range.start);
+
+    // Labels match case-insensitively, like reference links
+    let mut keys: HashMap<String, String> = HashMap::new();
+    for label in footnotes.keys() {
+        keys.entry(label.to_lowercase())
+            .or_insert_with(|| label.clone());
+    }
+    for (label, reference) in references {
+        let key = keys.entry(label.to_lowercase()).or_insert(label).clone();
+        footnotes
+            .entry(key.clone())
+            .or_insert_with(|| Footnote::new(key))
+            .references
+            .push(reference);
+    }
+
+    footnotes
+}
+
+/// Find `[^label]` text that the Markdown parser did not turn into a reference
+fn unresolved_references<S: SectionView>(
+    sections: &[S],
+    resolved: &[(String, FootnoteReference)],
+    skip: &[Range<usize>],
+    lines: &LineIndex<'_>,
+) -> Vec<(String, FootnoteReference)> {
+    let mut found = Vec::new();
+    let resolved: HashSet<Range<usize>> = resolved
+        .iter()
+        .map(|(_, r)| r.span.byte_range.clone())
+        .collect();
+
+    for (idx, section) in sections.iter().enumerate() {
+        if section.section_type() == SectionType::Code {
+            continue;
+        }
+        let base = section.span().byte_range.start;
+        for caps in FOOTNOTE_REF_REGEX.captures_iter(section.raw()) {
+            let (Some(full), Some(label)) = (caps.get(0), caps.get(1)) else {
+                continue;
+            };
+            let range = base + full.start()..base + full.end();
+            // Skip the label of the definition itself
+            let is_definition_label =
+                section.section_type() == SectionType::FootnoteDefinition && full.start() == 0;
+            let is_resolved = resolved.contains(&range);
+            let skipped = skip
+                .iter()
+                .any(|s| s.start < range.end && range.start < s.end);
+            if !is_definition_label && !is_resolved && !skipped {
+                found.push((
+                    label.as_str().to_string(),
+                    FootnoteReference {
+                        section_idx: Some(idx),
+                        span: lines.span(range),
+                    },
+                ));
+            }
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section::ParsedSection;
+
+    #[test]
+    fn test_footnote_status() {
+        let mut note = Footnote::new("1".to_string());
+        assert!(note.is_dangling());
+        assert!(note.is_unused());
+
+        note.definition_idx = Some(3);
+        note.references.push(FootnoteReference {
+            section_idx: Some(0),
+            span: SourceSpan::default(),
+        });
+        note.references.push(FootnoteReference {
+            section_idx: Some(0),
+            span: SourceSpan::default(),
+        });
+        assert!(!note.is_dangling());
+        assert!(!note.is_unused());
+        assert_eq!(note.referencing_sections(), vec![0]);
+    }
+
+    #[test]
+    fn test_unresolved_references_skip_code() {
+        let source = "See [^a] and `[^b]`";
+        let lines = LineIndex::new(source);
+        let section = ParsedSection::new(SectionType::Paragraph, String::new(), 0)
+            .with_raw(source.to_string())
+            .with_span(lines.span(0..source.len()));
+
+        let code_ranges = vec![Range { start: 13, end: 19 }];
+        let found = unresolved_references(&[section], &[], &code_ranges, &lines);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "a");
+        assert_eq!(found[0].1.span.byte_range, 4..8);
+    }
+
+    #[test]
+    fn test_labels_match_case_insensitively() {
+        let source = "[^Note] and [^NOTE] and [^other] and [^Other]\n\n[^note]: Text";
+        let lines = LineIndex::new(source);
+        let reference = ParsedSection::new(SectionType::Paragraph, String::new(), 0)
+            .with_raw(source[..45].to_string())
+            .with_span(lines.span(0..45));
+        let definition = ParsedSection::new(SectionType::FootnoteDefinition, "Text".to_string(), 1)
+            .with_raw(source[47..].to_string())
+            .with_span(lines.span(47..source.len()));
+
+        let footnotes = build_footnotes(
+            &[reference, definition],
+            &[("note".to_string(), 1, "Text".to_string())],
+            Vec::new(),
+            &[],
+            &lines,
+        );
+        assert_eq!(footnotes.keys().collect::<Vec<_>>(), vec!["note", "other"]);
+        assert_eq!(footnotes["note"].references.len(), 2);
+        assert_eq!(footnotes["other"].references.len(), 2);
+        assert_eq!(footnotes["note"].definition_idx, Some(1));
+    }
+}
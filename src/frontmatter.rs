@@ -21,6 +21,9 @@ use std::collections::HashMap;
 
 use crate::error::ParseError;
 
+/// Parsed frontmatter keys and values
+type Frontmatter = HashMap<String, serde_yaml::Value>;
+
 /// Strip frontmatter from content and parse it as YAML
 ///
 /// Returns a tuple of (remaining content, parsed frontmatter).
@@ -32,11 +35,21 @@ use crate::error::ParseError;
 pub fn strip_frontmatter(
     content: &str,
 ) -> Result<(String, Option<HashMap<String, serde_yaml::Value>>), ParseError> {
-    let trimmed = content.trim_start();
+    let (remaining, frontmatter) = split_frontmatter(content)?;
+    Ok((remaining.to_string(), frontmatter))
+}
+
+/// Split frontmatter from content without copying the remaining text
+///
+/// Like [`strip_frontmatter`], but the remaining content is returned as a
+/// slice of `content`.
+pub(crate) fn split_frontmatter(content: &str) -> Result<(&str, Option<Frontmatter>), ParseError> {
+    // Skip a byte order mark and leading whitespace
+    let trimmed = content.trim_start_matches('\u{feff}').trim_start();
 
     // Check if content starts with frontmatter delimiter
     if !trimmed.starts_with("---") {
-        return Ok((content.to_string(), None));
+        return Ok((content, None));
     }
 
     // Find the end of frontmatter - everything after the first "---"
@@ -48,21 +61,24 @@ pub fn strip_frontmatter(
         // It includes the leading newline from after_first_delimiter
         let yaml_content = &after_first_delimiter[..end_idx];
         // Remove leading newline if present
-        let yaml_content = yaml_content.strip_prefix('\n').unwrap_or(yaml_content);
+        let yaml_content = yaml_content
+            .strip_prefix("\r\n")
+            .or_else(|| yaml_content.strip_prefix('\n'))
+            .unwrap_or(yaml_content);
 
         let remaining_content = &after_first_delimiter[end_idx + 4..]; // Skip \n---
 
         // Skip any trailing newlines after the closing delimiter
-        let remaining = remaining_content.trim_start_matches('\n');
+        let remaining = remaining_content.trim_start_matches(['\r', '\n']);
 
         // Parse YAML (empty string parses to empty HashMap)
         let frontmatter: HashMap<String, serde_yaml::Value> = serde_yaml::from_str(yaml_content)
             .map_err(|e| ParseError::FrontmatterError(format!("Invalid YAML: {}", e)))?;
 
-        Ok((remaining.to_string(), Some(frontmatter)))
+        Ok((remaining, Some(frontmatter)))
     } else {
         // No closing delimiter found, treat as regular content
-        Ok((content.to_string(), None))
+        Ok((content, None))
     }
 }
 
@@ -203,4 +219,24 @@ count: 42
         assert!(frontmatter.is_some());
         assert!(remaining.contains("Some text with --- in it"));
     }
+
+    #[test]
+    fn test_frontmatter_with_bom_and_crlf() {
+        let content = "\u{feff}---\r\ntitle: Test\r\n---\r\n\r\n# Content";
+        let (remaining, frontmatter) = strip_frontmatter(content).unwrap();
+
+        let fm = frontmatter.unwrap();
+        assert_eq!(fm.get("title"), Some(&Value::String("Test".to_string())));
+        assert_eq!(remaining, "# Content");
+    }
+
+    #[test]
+    fn test_split_frontmatter_borrows_content() {
+        let content = "---\ntitle: Test\n---\n\n# Content";
+        let (remaining, frontmatter) = split_frontmatter(content).unwrap();
+
+        assert!(frontmatter.is_some());
+        assert_eq!(remaining, "# Content");
+        assert_eq!(&content[content.len() - remaining.len()..], remaining);
+    }
 }
@@ -0,0 +1,114 @@
+//! Raw HTML fragments and comments
+
+use std::ops::Range;
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::span::SourceSpan;
+
+/// Regex for HTML comments: `<!-- text -->`
+static HTML_COMMENT_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<!--(.*?)-->").expect("Invalid HTML comment regex"));
+
+/// An inline HTML fragment inside a section, such as `<span>` or `<br>`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtmlFragment {
+    /// HTML source of the fragment
+    pub html: String,
+    /// Index of the section containing the fragment
+    pub section_idx: Option<usize>,
+    /// Location of the fragment
+    pub span: SourceSpan,
+}
+
+/// An HTML comment from a block or inline position
+///
+/// # Example
+///
+/// ```
+/// use md_parser::MarkdownParser;
+///
+/// let doc = MarkdownParser::new()
+///     .parse("<!-- owner: team-a -->\n\n# Story")
+///     .unwrap();
+///
+/// let comment = &doc.html_comments[0];
+/// assert_eq!(comment.text, "owner: team-a");
+/// assert_eq!(comment.annotation(), Some(("owner", "team-a")));
+/// assert_eq!(doc.annotation("owner"), Some("team-a"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HtmlComment {
+    /// Comment text without the `<!--` and `-->` delimiters, trimmed
+    pub text: String,
+    /// Whether the comment appeared inline within other content
+    pub inline: bool,
+    /// Index of the section containing the comment
+    pub section_idx: Option<usize>,
+    /// Location of the full comment
+    pub span: SourceSpan,
+}
+
+impl HtmlComment {
+    /// Parse the comment as a `key: value` annotation
+    ///
+    /// The key must be a single word of letters, digits, `-`, `_` or `.`.
+    pub fn annotation(&self) -> Option<(&str, &str)> {
+        let (key, value) = self.text.split_once(':')?;
+        let key = key.trim();
+        let is_key = !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        is_key.then(|| (key, value.trim()))
+    }
+}
+
+/// Find every comment in `html`, returning its byte range and trimmed text
+pub(crate) fn comments_in(html: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    HTML_COMMENT_REGEX.captures_iter(html).filter_map(|caps| {
+        let full = caps.get(0)?;
+        let text = caps.get(1)?;
+        Some((full.range(), text.as_str().trim()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(text: &str) -> HtmlComment {
+        HtmlComment {
+            text: text.to_string(),
+            inline: false,
+            section_idx: None,
+            span: SourceSpan::default(),
+        }
+    }
+
+    #[test]
+    fn test_annotation() {
+        assert_eq!(
+            comment("owner: team-a").annotation(),
+            Some(("owner", "team-a"))
+        );
+        assert_eq!(
+            comment("story.status:  draft ").annotation(),
+            Some(("story.status", "draft"))
+        );
+        assert_eq!(comment("TODO fix: later").annotation(), None);
+        assert_eq!(comment("just a note").annotation(), None);
+        assert_eq!(comment(": value").annotation(), None);
+    }
+
+    #[test]
+    fn test_comments_in() {
+        let found: Vec<_> = comments_in("<div><!-- a --> <!--\nb\n--></div>").collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0], (5..15, "a"));
+        assert_eq!(found[1].1, "b");
+    }
+}
@@ -0,0 +1,309 @@
+//! Section ID generation strategies
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::anchor::slugify;
+use crate::section::{ParsedSection, SectionType, SectionView};
+
+/// Namespace for content-derived UUID v5 section IDs
+const SECTION_NAMESPACE: Uuid = Uuid::from_bytes([
+    0x6d, 0x64, 0x2d, 0x70, 0x61, 0x72, 0x73, 0x65, 0x72, 0x2d, 0x73, 0x65, 0x63, 0x74, 0x69, 0x6f,
+]);
+
+/// Signature of a user-supplied section ID function
+pub type IdFn = dyn Fn(&IdContext<'_>) -> String + Send + Sync;
+
+/// How section IDs are generated
+///
+/// Every strategy except `Random` gives the same IDs each time the same
+/// document is parsed.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{IdStrategy, MarkdownParser};
+///
+/// let parser = MarkdownParser::builder()
+///     .id_strategy(IdStrategy::HeadingSlug)
+///     .build();
+/// let doc = parser.parse("# Story\n\n## Tasks\n\nFirst\n\nSecond").unwrap();
+///
+/// assert_eq!(doc.sections[1].id, "story/tasks");
+/// assert_eq!(doc.sections[3].id, "story/tasks/paragraph-2");
+///
+/// let again = parser.parse("# Story\n\n## Tasks\n\nFirst\n\nSecond").unwrap();
+/// assert_eq!(again.sections[3].id, doc.sections[3].id);
+/// ```
+#[derive(Clone, Default)]
+pub enum IdStrategy {
+    /// Random UUID v4 (different on every parse)
+    #[default]
+    Random,
+    /// UUID v5 derived from the heading path, section type and content
+    ContentHash,
+    /// Path of heading anchors, e.g. `story/tasks--subtasks/list-1`
+    HeadingSlug,
+    /// Leave IDs empty
+    None,
+    /// Call a user-supplied function for each section
+    Custom(Arc<IdFn>),
+}
+
+impl IdStrategy {
+    /// Use a closure to generate section IDs
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(&IdContext<'_>) -> String + Send + Sync + 'static,
+    {
+        IdStrategy::Custom(Arc::new(f))
+    }
+
+    /// Get string representation of the strategy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdStrategy::Random => "random",
+            IdStrategy::ContentHash => "content_hash",
+            IdStrategy::HeadingSlug => "heading_slug",
+            IdStrategy::None => "none",
+            IdStrategy::Custom(_) => "custom",
+        }
+    }
+
+    /// Parse a strategy name (`custom` is not accepted)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "random" => Some(IdStrategy::Random),
+            "content_hash" => Some(IdStrategy::ContentHash),
+            "heading_slug" => Some(IdStrategy::HeadingSlug),
+            "none" => Some(IdStrategy::None),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for IdStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IdStrategy::{}", self.as_str())
+    }
+}
+
+/// Information passed to a custom ID function
+#[derive(Debug)]
+pub struct IdContext<'a> {
+    /// The section to generate an ID for (its `id` is still empty)
+    pub section: &'a ParsedSection,
+    /// Index of the section in the document
+    pub index: usize,
+    /// Content of the enclosing headings, outermost first
+    pub heading_path: Vec<&'a str>,
+}
+
+/// Assign IDs to all sections using `strategy`
+pub(crate) fn assign_ids(sections: &mut [ParsedSection], strategy: &IdStrategy) {
+    IdAssigner::new(strategy.clone()).assign(sections);
+}
+
+/// A heading that encloses the sections being assigned
+#[derive(Debug)]
+struct OpenHeading {
+    level: u8,
+    index: usize,
+    content: String,
+    id: String,
+}
+
+/// Assigns section IDs in document order, one batch of sections at a time
+///
+/// Keeps the enclosing headings and occurrence counts between calls so a
+/// document streamed in chunks gets the same IDs as a full parse.
+#[derive(Debug)]
+pub(crate) struct IdAssigner {
+    strategy: IdStrategy,
+    /// Enclosing headings, outermost first
+    open: Vec<OpenHeading>,
+    /// Occurrences of each content-hash key (keyed by the key's own UUID)
+    seen: HashMap<Uuid, usize>,
+    /// Heading-slug numbering per (parent index, section type)
+    counts: HashMap<(Option<usize>, SectionType), usize>,
+    /// Document index of the next section
+    next: usize,
+}
+
+impl IdAssigner {
+    pub(crate) fn new(strategy: IdStrategy) -> Self {
+        Self {
+            strategy,
+            open: Vec::new(),
+            seen: HashMap::new(),
+            counts: HashMap::new(),
+            next: 0,
+        }
+    }
+
+    /// Assign IDs to the next sections of the document
+    pub(crate) fn assign<S: SectionView>(&mut self, sections: &mut [S]) {
+        for section in sections {
+            let index = self.next;
+            self.next += 1;
+
+            let heading_level = (section.section_type() == SectionType::Heading)
+                .then(|| section.level().unwrap_or(1));
+            if let Some(level) = heading_level {
+                while self.open.last().is_some_and(|h| h.level >= level) {
+                    let closed = self.open.pop().map(|h| h.index);
+                    self.counts.retain(|(parent, _), _| *parent != closed);
+                }
+            }
+
+            let id = self.id_for(section, index);
+
+            if let Some(level) = heading_level {
+                self.open.push(OpenHeading {
+                    level,
+                    index,
+                    content: section.content().to_string(),
+                    id: id.clone(),
+                });
+            }
+            section.set_id(id);
+        }
+    }
+
+    fn id_for<S: SectionView>(&mut self, section: &S, index: usize) -> String {
+        match &self.strategy {
+            IdStrategy::Random => Uuid::new_v4().to_string(),
+            IdStrategy::None => String::new(),
+            IdStrategy::ContentHash => {
+                let path = self.heading_path().join("\u{1f}");
+                let key = format!(
+                    "{}\u{1e}{}\u{1e}{}",
+                    path,
+                    section.section_type().as_str(),
+                    section.content()
+                );
+                // Identical sections under the same headings get distinct IDs
+                let n = self
+                    .seen
+                    .entry(Uuid::new_v5(&SECTION_NAMESPACE, key.as_bytes()))
+                    .or_default();
+                *n += 1;
+                let name = format!("{}\u{1e}{}", key, n);
+                Uuid::new_v5(&SECTION_NAMESPACE, name.as_bytes()).to_string()
+            }
+            IdStrategy::HeadingSlug => {
+                let parent = self.open.last();
+                let name = match section.section_type() {
+                    SectionType::Heading => section
+                        .anchor()
+                        .map(String::from)
+                        .unwrap_or_else(|| slugify(section.content())),
+                    st => {
+                        let n = self
+                            .counts
+                            .entry((parent.map(|h| h.index), st))
+                            .or_default();
+                        *n += 1;
+                        format!("{}-{}", st.as_str(), n)
+                    }
+                };
+                match parent {
+                    Some(parent) => format!("{}/{}", parent.id, name),
+                    None => name,
+                }
+            }
+            IdStrategy::Custom(f) => f(&IdContext {
+                section: &section.to_owned_section(),
+                index,
+                heading_path: self.heading_path(),
+            }),
+        }
+    }
+
+    /// Content of the enclosing headings, outermost first
+    fn heading_path(&self) -> Vec<&str> {
+        self.open.iter().map(|h| h.content.as_str()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections() -> Vec<ParsedSection> {
+        vec![
+            ParsedSection::new(SectionType::Heading, "Story".into(), 0).with_level(1),
+            ParsedSection::new(SectionType::Paragraph, "Same".into(), 1),
+            ParsedSection::new(SectionType::Paragraph, "Same".into(), 2),
+            ParsedSection::new(SectionType::Heading, "Tasks".into(), 3).with_level(2),
+            ParsedSection::new(SectionType::Paragraph, "Same".into(), 4),
+        ]
+    }
+
+    fn ids(strategy: &IdStrategy) -> Vec<String> {
+        let mut sections = sections();
+        assign_ids(&mut sections, strategy);
+        sections.into_iter().map(|s| s.id).collect()
+    }
+
+    #[test]
+    fn test_content_hash_stable_and_unique() {
+        let first = ids(&IdStrategy::ContentHash);
+        assert_eq!(first, ids(&IdStrategy::ContentHash));
+
+        let unique: std::collections::HashSet<_> = first.iter().collect();
+        assert_eq!(unique.len(), first.len());
+        assert!(Uuid::parse_str(&first[0]).is_ok());
+    }
+
+    #[test]
+    fn test_heading_slug() {
+        assert_eq!(
+            ids(&IdStrategy::HeadingSlug),
+            vec![
+                "story",
+                "story/paragraph-1",
+                "story/paragraph-2",
+                "story/tasks",
+                "story/tasks/paragraph-1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_none_and_random() {
+        assert!(ids(&IdStrategy::None).iter().all(String::is_empty));
+        assert_ne!(ids(&IdStrategy::Random), ids(&IdStrategy::Random));
+    }
+
+    #[test]
+    fn test_custom() {
+        let strategy =
+            IdStrategy::custom(|ctx| format!("{}:{}", ctx.heading_path.join(">"), ctx.index));
+        assert_eq!(ids(&strategy)[4], "Story>Tasks:4");
+    }
+
+    #[test]
+    fn test_assign_in_batches() {
+        for strategy in [IdStrategy::ContentHash, IdStrategy::HeadingSlug] {
+            let mut batched = sections();
+            let (first, rest) = batched.split_at_mut(2);
+            let mut assigner = IdAssigner::new(strategy.clone());
+            assigner.assign(first);
+            assigner.assign(rest);
+
+            let batched: Vec<String> = batched.into_iter().map(|s| s.id).collect();
+            assert_eq!(batched, ids(&strategy));
+        }
+    }
+
+    #[test]
+    fn test_from_name() {
+        for name in ["random", "content_hash", "heading_slug", "none"] {
+            assert_eq!(IdStrategy::from_name(name).unwrap().as_str(), name);
+        }
+        assert!(IdStrategy::from_name("custom").is_none());
+    }
+}
@@ -0,0 +1,898 @@
+//! Incremental re-parsing after text edits
+//!
+//! The document is kept as chunks split at top-level headings, the same way
+//! [`SectionStream`](crate::SectionStream) splits its input. An edit
+//! re-parses the chunks it touches, continuing until the chunk boundaries
+//! line up with the old ones again, and shifts everything after them.
+//! References to link and footnote definitions in other chunks resolve
+//! against the definitions of the whole document.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+
+use pulldown_cmark::CowStr;
+use uuid::Uuid;
+
+use crate::anchor::Slugger;
+use crate::checklist::ChecklistItem;
+use crate::document::ParsedDocument;
+use crate::error::ParseError;
+use crate::fields::merge_fields;
+use crate::footnote::{Footnote, FootnoteReference};
+use crate::html::{HtmlComment, HtmlFragment};
+use crate::id::{assign_ids, IdStrategy};
+use crate::link::{LinkDefinition, ParsedLink};
+use crate::parser::MarkdownParser;
+use crate::section::ParsedSection;
+use crate::span::SourceSpan;
+use crate::stream::{shift_section, ChunkScanner};
+use crate::wiki::WikiLink;
+
+/// Sections that changed in an edit
+///
+/// Sections whose type, level and content are unchanged are not reported,
+/// even if they moved.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeSet {
+    /// Indices of new sections in the updated document
+    pub added: Vec<usize>,
+    /// Indices of deleted sections in the previous document
+    pub removed: Vec<usize>,
+    /// Indices of changed sections in the updated document
+    pub modified: Vec<usize>,
+}
+
+impl ChangeSet {
+    /// Check if no section changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// A parsed document that can be updated with text edits
+///
+/// Untouched sections keep their IDs. With `IdStrategy::Random`, modified
+/// sections keep theirs too; the other strategies recompute every ID, as a
+/// full parse would. Heading anchors of untouched sections never change.
+///
+/// An edit re-parses only the chunks it touches, but the document-wide
+/// results (IDs other than random ones, edges, variables, tags, mentions,
+/// fields and the title) are then rebuilt from every section, which takes
+/// time linear in the number of sections. An edit that adds, removes or
+/// changes a link reference or footnote definition re-parses the whole
+/// document, as does every edit to a document that defines the same link
+/// label twice.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{IncrementalParser, MarkdownParser};
+///
+/// let source = "# Notes\n\nFirst\n\n# Tasks\n\n- [ ] Write\n";
+/// let mut incremental = IncrementalParser::new(MarkdownParser::new(), source).unwrap();
+/// let task_id = incremental.document().sections[3].id.clone();
+///
+/// // Replace "First" with "Updated"
+/// let changes = incremental.edit(9..14, "Updated").unwrap();
+/// assert_eq!(changes.modified, vec![1]);
+///
+/// let doc = incremental.document();
+/// assert_eq!(doc.sections[1].content, "Updated");
+/// assert_eq!(doc.sections[3].id, task_id);
+/// assert_eq!(doc.checklist_items[0].span.byte_range, 27..38);
+/// ```
+pub struct IncrementalParser {
+    parser: MarkdownParser,
+    source: String,
+    /// Byte offset where each chunk starts
+    chunk_starts: Vec<usize>,
+    /// Index of the first section of each chunk
+    chunk_sections: Vec<usize>,
+    document: ParsedDocument,
+}
+
+impl IncrementalParser {
+    /// Parse `source` with `parser`'s settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the markdown structure is invalid.
+    pub fn new(parser: MarkdownParser, source: &str) -> Result<Self, ParseError> {
+        let mut incremental = Self {
+            parser,
+            source: String::new(),
+            chunk_starts: Vec::new(),
+            chunk_sections: Vec::new(),
+            document: ParsedDocument::new(),
+        };
+        incremental.reparse(source.to_string())?;
+        Ok(incremental)
+    }
+
+    /// Get the current source text
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Get the current parsed document
+    pub fn document(&self) -> &ParsedDocument {
+        &self.document
+    }
+
+    /// Take the parsed document
+    pub fn into_document(self) -> ParsedDocument {
+        self.document
+    }
+
+    /// Replace the bytes in `range` with `replacement` and re-parse
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidEdit` if `range` is out of bounds or not on
+    /// character boundaries, or another `ParseError` if re-parsing fails.
+    /// The document is unchanged on error.
+    pub fn edit(
+        &mut self,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Result<ChangeSet, ParseError> {
+        if range.start > range.end
+            || !self.source.is_char_boundary(range.start)
+            || !self.source.is_char_boundary(range.end)
+        {
+            return Err(ParseError::InvalidEdit(format!(
+                "{:?} is not a valid range in {} bytes of source",
+                range,
+                self.source.len()
+            )));
+        }
+
+        let mut source = String::with_capacity(self.source.len() + replacement.len());
+        source.push_str(&self.source[..range.start]);
+        source.push_str(replacement);
+        source.push_str(&self.source[range.end..]);
+        let delta = replacement.len() as isize - range.len() as isize;
+        let line_delta = replacement.matches('\n').count() as isize
+            - self.source[range.clone()].matches('\n').count() as isize;
+        let Some(external) = ExternalDefinitions::of(&self.document) else {
+            return self.reparse(source);
+        };
+
+        // Start at the chunk containing the edit, or the one before it if the
+        // edit touches a chunk's first line
+        let mut first = self
+            .chunk_starts
+            .partition_point(|&start| start <= range.start)
+            .saturating_sub(1);
+        if first > 0 && self.chunk_starts[first] == range.start {
+            first -= 1;
+        }
+        let region_start = self.chunk_starts.get(first).copied().unwrap_or(0);
+
+        // Find new chunk starts until one matches an old start after the edit
+        let edit_end = range.start + replacement.len();
+        let mut scanner = if source[..region_start].trim().is_empty() {
+            ChunkScanner::default()
+        } else {
+            ChunkScanner::after_content()
+        };
+        let mut starts = vec![region_start];
+        let mut last = self.chunk_starts.len();
+        let mut region_end = source.len();
+        let mut pos = region_start;
+        for line in source[region_start..].split_inclusive('\n') {
+            if scanner.is_boundary(line) && pos > region_start {
+                let resync = (pos >= edit_end)
+                    .then(|| {
+                        self.chunk_starts
+                            .binary_search(&pos.wrapping_add_signed(-delta))
+                    })
+                    .and_then(Result::ok)
+                    .filter(|&idx| idx > first);
+                if let Some(idx) = resync {
+                    last = idx;
+                    region_end = pos;
+                    break;
+                }
+                starts.push(pos);
+            }
+            pos += line.len();
+        }
+
+        // Re-parse the new chunks, keeping anchors unique across the document
+        let old_sections = self.chunk_sections.get(first).copied().unwrap_or(0)
+            ..self
+                .chunk_sections
+                .get(last)
+                .copied()
+                .unwrap_or(self.document.sections.len());
+        let mut slugger = Slugger::default();
+        for (idx, section) in self.document.sections.iter().enumerate() {
+            if let (false, Some(anchor)) = (old_sections.contains(&idx), &section.anchor) {
+                slugger.reserve(anchor);
+            }
+        }
+        let mut lines = source[..region_start].matches('\n').count() as isize;
+        let mut fresh = ParsedDocument::new();
+        let mut section_starts = Vec::with_capacity(starts.len());
+        for (idx, &start) in starts.iter().enumerate() {
+            let end = starts.get(idx + 1).copied().unwrap_or(region_end);
+            let text = &source[start..end];
+            let mut doc = self.parser.parse_chunk(text, &mut slugger, &external)?;
+            let offset = Offset {
+                bytes: start as isize,
+                lines,
+                sections: (old_sections.start + fresh.sections.len()) as isize,
+            };
+            section_starts.push(old_sections.start + fresh.sections.len());
+            lines += text.matches('\n').count() as isize;
+            append_chunk(&mut fresh, &mut doc, &offset);
+            #[cfg(feature = "frontmatter")]
+            if start == 0 {
+                fresh.frontmatter = doc.frontmatter;
+            }
+        }
+
+        // Splice the new chunks into the document
+        let old_region_end = self
+            .chunk_starts
+            .get(last)
+            .copied()
+            .unwrap_or(self.source.len());
+
+        // References elsewhere may resolve differently if a definition changed
+        let old_definitions = definition_keys(
+            self.document
+                .link_definitions
+                .iter()
+                .filter(|d| (region_start..old_region_end).contains(&d.span.byte_range.start)),
+            self.document.footnotes.values().filter(|f| {
+                f.definition_idx
+                    .is_some_and(|idx| old_sections.contains(&idx))
+            }),
+        );
+        let new_definitions = definition_keys(
+            &fresh.link_definitions,
+            fresh
+                .footnotes
+                .values()
+                .filter(|f| f.definition_idx.is_some()),
+        );
+        if old_definitions != new_definitions {
+            return self.reparse(source);
+        }
+
+        let splice = Splice {
+            bytes: region_start..old_region_end,
+            sections: old_sections.clone(),
+            offset: Offset {
+                bytes: delta,
+                lines: line_delta,
+                sections: fresh.sections.len() as isize - old_sections.len() as isize,
+            },
+        };
+        let new_sections = old_sections.start..old_sections.start + fresh.sections.len();
+        let mut document = std::mem::take(&mut self.document);
+        let old: Vec<ParsedSection> = document
+            .sections
+            .splice(old_sections.clone(), fresh.sections)
+            .collect();
+        for (idx, section) in document.sections.iter_mut().enumerate() {
+            if idx >= new_sections.end {
+                shift_section(section, delta, line_delta);
+            }
+            section.order_idx = idx as u32;
+        }
+        splice.apply(&mut document.checklist_items, fresh.checklist_items);
+        splice.apply(&mut document.links, fresh.links);
+        splice.apply(&mut document.images, fresh.images);
+        splice.apply(&mut document.link_definitions, fresh.link_definitions);
+        splice.apply(&mut document.wiki_links, fresh.wiki_links);
+        splice.apply(&mut document.inline_html, fresh.inline_html);
+        splice.apply(&mut document.html_comments, fresh.html_comments);
+        splice.apply_footnotes(&mut document.footnotes, fresh.footnotes);
+        #[cfg(feature = "frontmatter")]
+        if first == 0 {
+            document.frontmatter = fresh.frontmatter;
+        }
+
+        let changes = diff_sections(&old, &mut document.sections, new_sections.clone());
+        match self.parser.id_strategy() {
+            IdStrategy::Random => {}
+            strategy => assign_ids(&mut document.sections, strategy),
+        }
+        document.variables = document
+            .sections
+            .iter()
+            .flat_map(|s| s.variables.iter().cloned())
+            .collect();
+        document.variables.sort();
+        document.variables.dedup();
+        document.tags = document.frontmatter_tags().into_iter().collect();
+        document.tags.extend(
+            document
+                .sections
+                .iter()
+                .flat_map(|s| s.tags.iter().cloned()),
+        );
+        document.mentions = document
+            .sections
+            .iter()
+            .flat_map(|s| s.mentions.iter().cloned())
+            .collect();
+        document.fields = merge_fields(document.sections.iter().map(|s| &s.fields));
+        document.edges = self.parser.generate_edges(&document.sections);
+        (document.title, document.title_source) = self
+            .parser
+            .resolve_title(
+                &document.sections,
+                |key| document.frontmatter_string(key),
+                None,
+            )
+            .unzip();
+
+        // Record the new chunk boundaries
+        let tail_starts = self.chunk_starts[last..]
+            .iter()
+            .map(|&start| start.wrapping_add_signed(delta));
+        let tail_sections = self.chunk_sections[last..]
+            .iter()
+            .map(|&idx| idx.wrapping_add_signed(splice.offset.sections));
+        self.chunk_starts = self.chunk_starts[..first]
+            .iter()
+            .copied()
+            .chain(starts)
+            .chain(tail_starts)
+            .collect();
+        self.chunk_sections = self.chunk_sections[..first]
+            .iter()
+            .copied()
+            .chain(section_starts)
+            .chain(tail_sections)
+            .collect();
+        self.source = source;
+        self.document = document;
+
+        Ok(changes)
+    }
+
+    /// Parse the whole of `source` at once and record its chunk boundaries
+    fn reparse(&mut self, source: String) -> Result<ChangeSet, ParseError> {
+        let mut document = self
+            .parser
+            .parse_without_ids(&source, None, &mut Slugger::default())?;
+        let old = std::mem::take(&mut self.document.sections);
+        let region = 0..document.sections.len();
+        let changes = diff_sections(&old, &mut document.sections, region);
+        match self.parser.id_strategy() {
+            IdStrategy::Random => {}
+            strategy => assign_ids(&mut document.sections, strategy),
+        }
+
+        let mut scanner = ChunkScanner::default();
+        let mut pos = 0;
+        self.chunk_starts = vec![0];
+        for line in source.split_inclusive('\n') {
+            if scanner.is_boundary(line) && pos > 0 {
+                self.chunk_starts.push(pos);
+            }
+            pos += line.len();
+        }
+        self.chunk_sections = self
+            .chunk_starts
+            .iter()
+            .map(|&start| {
+                document
+                    .sections
+                    .partition_point(|s| s.span.byte_range.start < start)
+            })
+            .collect();
+        self.source = source;
+        self.document = document;
+
+        Ok(changes)
+    }
+}
+
+/// Link reference and footnote definitions of a whole document, for
+/// resolving references in a chunk to definitions in other chunks
+#[derive(Debug, Default)]
+pub(crate) struct ExternalDefinitions {
+    /// URL and title of each link reference definition, by normalized label
+    links: HashMap<String, (String, String)>,
+    /// Lowercased labels of the defined footnotes
+    footnotes: HashSet<String>,
+}
+
+impl ExternalDefinitions {
+    /// Collect the definitions in `document`, or `None` if a link label is
+    /// defined twice, since a chunk would then prefer its own definition
+    fn of(document: &ParsedDocument) -> Option<Self> {
+        let mut links = HashMap::new();
+        for definition in &document.link_definitions {
+            let target = (
+                definition.url.clone(),
+                definition.title.clone().unwrap_or_default(),
+            );
+            if links
+                .insert(normalize_label(&definition.label), target)
+                .is_some()
+            {
+                return None;
+            }
+        }
+        let footnotes = document
+            .footnotes
+            .values()
+            .filter(|f| f.definition_idx.is_some())
+            .map(|f| f.label.to_lowercase())
+            .collect();
+        Some(Self { links, footnotes })
+    }
+
+    /// Get the URL and title for a reference link label
+    pub(crate) fn resolve_link<'a>(&self, label: &str) -> Option<(CowStr<'a>, CowStr<'a>)> {
+        let (url, title) = self.links.get(&normalize_label(label))?;
+        Some((url.clone().into(), title.clone().into()))
+    }
+
+    /// Match a `[^label]` reference to a defined footnote at the start of
+    /// `text`, returning the label and the length of the reference
+    pub(crate) fn footnote_at<'t>(&self, text: &'t str) -> Option<(&'t str, usize)> {
+        let rest = text.strip_prefix("[^")?;
+        let label = &rest[..rest.find(']')?];
+        let valid = !label.is_empty() && !label.contains(char::is_whitespace);
+        (valid && self.footnotes.contains(&label.to_lowercase())).then(|| (label, label.len() + 3))
+    }
+}
+
+/// Normalize a link label the way reference matching does: case-insensitive,
+/// with runs of whitespace collapsed
+fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Sorted labels and targets of link reference and footnote definitions
+fn definition_keys<'a>(
+    links: impl IntoIterator<Item = &'a LinkDefinition>,
+    footnotes: impl IntoIterator<Item = &'a Footnote>,
+) -> Vec<(String, String, Option<String>)> {
+    let mut keys: Vec<_> = links
+        .into_iter()
+        .map(|d| (normalize_label(&d.label), d.url.clone(), d.title.clone()))
+        .chain(
+            footnotes
+                .into_iter()
+                .map(|f| (format!("^{}", f.label.to_lowercase()), String::new(), None)),
+        )
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Compare the old and new sections of the re-parsed region
+///
+/// Unchanged sections at either end keep their IDs; the rest are paired up
+/// in order as modified (keeping the old ID), then added or removed.
+fn diff_sections(
+    old: &[ParsedSection],
+    sections: &mut [ParsedSection],
+    region: Range<usize>,
+) -> ChangeSet {
+    let new = &mut sections[region.clone()];
+    let same = |a: &ParsedSection, b: &ParsedSection| {
+        a.section_type == b.section_type && a.level == b.level && a.content == b.content
+    };
+    let max = old.len().min(new.len());
+    let prefix = (0..max).take_while(|&i| same(&old[i], &new[i])).count();
+    let suffix = (0..max - prefix)
+        .take_while(|&i| same(&old[old.len() - 1 - i], &new[new.len() - 1 - i]))
+        .count();
+    let paired = old.len().min(new.len()) - suffix;
+
+    let mut changes = ChangeSet::default();
+    for i in 0..new.len() {
+        let old_idx = if i < paired {
+            Some(i)
+        } else if i >= new.len() - suffix {
+            Some(i + old.len() - new.len())
+        } else {
+            None
+        };
+        match old_idx {
+            Some(j) => {
+                new[i].id = old[j].id.clone();
+                if i >= prefix && i < paired {
+                    changes.modified.push(region.start + i);
+                }
+            }
+            None => {
+                new[i].id = Uuid::new_v4().to_string();
+                changes.added.push(region.start + i);
+            }
+        }
+    }
+    changes.removed = (paired..old.len() - suffix)
+        .map(|j| region.start + j)
+        .collect();
+    changes
+}
+
+/// Amounts to move parsed elements by
+struct Offset {
+    bytes: isize,
+    lines: isize,
+    sections: isize,
+}
+
+impl Offset {
+    fn apply<T: Located>(&self, item: &mut T) {
+        item.span_mut().shift(self.bytes, self.lines);
+        if let Some(Some(idx)) = item.section_idx_mut() {
+            *idx = idx.wrapping_add_signed(self.sections);
+        }
+    }
+
+    /// Move `items` and append them to `into`
+    fn append<T: Located>(&self, items: &mut Vec<T>, into: &mut Vec<T>) {
+        for item in items.iter_mut() {
+            self.apply(item);
+        }
+        into.append(items);
+    }
+}
+
+/// Replacement of a byte region and its sections
+struct Splice {
+    /// Old byte range of the re-parsed chunks
+    bytes: Range<usize>,
+    /// Old section indices of the re-parsed chunks
+    sections: Range<usize>,
+    /// How far later elements move
+    offset: Offset,
+}
+
+impl Splice {
+    /// Replace the items in the old region with `new` and shift later items
+    fn apply<T: Located>(&self, items: &mut Vec<T>, new: Vec<T>) {
+        let old = std::mem::take(items);
+        let (mut before, mut after) = (Vec::new(), Vec::new());
+        for mut item in old {
+            let start = item.span_mut().byte_range.start;
+            if start < self.bytes.start {
+                before.push(item);
+            } else if start >= self.bytes.end {
+                self.offset.apply(&mut item);
+                after.push(item);
+            }
+        }
+        before.extend(new);
+        before.extend(after);
+        *items = before;
+    }
+
+    /// Merge re-parsed footnotes into the existing ones
+    fn apply_footnotes(
+        &self,
+        footnotes: &mut BTreeMap<String, Footnote>,
+        new: BTreeMap<String, Footnote>,
+    ) {
+        for footnote in footnotes.values_mut() {
+            self.apply(&mut footnote.references, Vec::new());
+            match footnote.definition_idx {
+                Some(idx) if self.sections.contains(&idx) => {
+                    footnote.definition_idx = None;
+                    footnote.content = None;
+                }
+                Some(idx) if idx >= self.sections.end => {
+                    footnote.definition_idx = Some(idx.wrapping_add_signed(self.offset.sections));
+                }
+                _ => {}
+            }
+        }
+
+        for (label, footnote) in new {
+            let key = footnotes
+                .keys()
+                .find(|k| k.to_lowercase() == label.to_lowercase())
+                .cloned();
+            match key.and_then(|k| footnotes.remove(&k)) {
+                Some(mut existing) => {
+                    if footnote.definition_idx.is_some() {
+                        existing.label = footnote.label;
+                        existing.content = footnote.content;
+                        existing.definition_idx = footnote.definition_idx;
+                    }
+                    existing.references.extend(footnote.references);
+                    existing.references.sort_by_key(|r| r.span.byte_range.start);
+                    footnotes.insert(existing.label.clone(), existing);
+                }
+                None => {
+                    footnotes.insert(label, footnote);
+                }
+            }
+        }
+
+        footnotes.retain(|_, f| f.definition_idx.is_some() || !f.references.is_empty());
+    }
+}
+
+/// Move a chunk's elements to their place in the document and append them
+fn append_chunk(fresh: &mut ParsedDocument, doc: &mut ParsedDocument, offset: &Offset) {
+    for section in &mut doc.sections {
+        shift_section(section, offset.bytes, offset.lines);
+    }
+    fresh.sections.append(&mut doc.sections);
+
+    offset.append(&mut doc.checklist_items, &mut fresh.checklist_items);
+    offset.append(&mut doc.links, &mut fresh.links);
+    offset.append(&mut doc.images, &mut fresh.images);
+    offset.append(&mut doc.link_definitions, &mut fresh.link_definitions);
+    offset.append(&mut doc.wiki_links, &mut fresh.wiki_links);
+    offset.append(&mut doc.inline_html, &mut fresh.inline_html);
+    offset.append(&mut doc.html_comments, &mut fresh.html_comments);
+
+    for (label, mut footnote) in std::mem::take(&mut doc.footnotes) {
+        for reference in &mut footnote.references {
+            offset.apply(reference);
+        }
+        footnote.definition_idx = footnote
+            .definition_idx
+            .map(|idx| idx.wrapping_add_signed(offset.sections));
+        fresh.footnotes.insert(label, footnote);
+    }
+}
+
+/// A parsed element with a source location
+trait Located {
+    fn span_mut(&mut self) -> &mut SourceSpan;
+
+    /// Index of the owning section, for elements that record one
+    fn section_idx_mut(&mut self) -> Option<&mut Option<usize>> {
+        None
+    }
+}
+
+macro_rules! located {
+    ($($ty:ty),*) => {
+        $(impl Located for $ty {
+            fn span_mut(&mut self) -> &mut SourceSpan {
+                &mut self.span
+            }
+
+            fn section_idx_mut(&mut self) -> Option<&mut Option<usize>> {
+                Some(&mut self.section_idx)
+            }
+        })*
+    };
+}
+
+located!(
+    ChecklistItem,
+    ParsedLink,
+    HtmlFragment,
+    HtmlComment,
+    FootnoteReference,
+    WikiLink
+);
+
+impl Located for LinkDefinition {
+    fn span_mut(&mut self) -> &mut SourceSpan {
+        &mut self.span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "# Story\n\nIntro {{name}} #draft with [a link](https://a.example).\nStatus:: draft\n\n\
+                          ## Tasks\n\n- [ ] One @me\n- [x] Two #done\n\n<!-- owner: me -->\n\n\
+                          ## Notes\n\n```\n# not a heading\n```\n\nDone ✓ see [[Design#Goals|goals]]\n";
+
+    fn parser() -> MarkdownParser {
+        MarkdownParser::builder()
+            .id_strategy(IdStrategy::HeadingSlug)
+            .build()
+    }
+
+    /// Apply `edit` and check the result against a full parse
+    fn check(incremental: &mut IncrementalParser, range: Range<usize>, text: &str) -> ChangeSet {
+        let changes = incremental.edit(range, text).unwrap();
+        let expected = parser().parse(incremental.source()).unwrap();
+        let doc = incremental.document();
+
+        assert_eq!(doc.sections.len(), expected.sections.len());
+        for (section, expected) in doc.sections.iter().zip(&expected.sections) {
+            assert_eq!(section.section_type, expected.section_type);
+            assert_eq!(section.content, expected.content);
+            assert_eq!(section.span, expected.span);
+            assert_eq!(section.id, expected.id);
+            assert_eq!(section.anchor, expected.anchor);
+            assert_eq!(section.order_idx, expected.order_idx);
+            assert_eq!(section.tags, expected.tags);
+        }
+        assert_eq!(doc.checklist_items, expected.checklist_items);
+        assert_eq!(doc.links, expected.links);
+        assert_eq!(doc.link_definitions, expected.link_definitions);
+        assert_eq!(doc.footnotes, expected.footnotes);
+        assert_eq!(doc.wiki_links, expected.wiki_links);
+        assert_eq!(doc.html_comments, expected.html_comments);
+        assert_eq!(doc.variables, expected.variables);
+        assert_eq!(doc.tags, expected.tags);
+        assert_eq!(doc.mentions, expected.mentions);
+        assert_eq!(doc.fields, expected.fields);
+        assert_eq!(doc.edges.len(), expected.edges.len());
+        assert_eq!(doc.title, expected.title);
+        changes
+    }
+
+    fn offset(incremental: &IncrementalParser, needle: &str) -> usize {
+        incremental.source().find(needle).unwrap()
+    }
+
+    #[test]
+    fn test_edits_match_full_parse() {
+        let mut inc = IncrementalParser::new(parser(), SOURCE).unwrap();
+
+        let at = offset(&inc, "Intro");
+        check(&mut inc, at..at, "New ");
+        let at = offset(&inc, "## Notes");
+        check(&mut inc, at..at + 3, "");
+        let at = offset(&inc, "Notes");
+        check(&mut inc, at..at, "## ");
+        let at = offset(&inc, "- [x] Two");
+        check(&mut inc, at..at, "```\n");
+        let at = offset(&inc, "```\n- [x]");
+        check(&mut inc, at..at + 4, "");
+        let end = inc.source().len();
+        check(&mut inc, end..end, "\n# Appendix\n\nMore\n");
+        let at = offset(&inc, "✓");
+        check(&mut inc, at..at + '✓'.len_utf8(), "!\n\n# Split\n");
+        let end = inc.source().len();
+        check(&mut inc, 0..end, "");
+        check(&mut inc, 0..0, SOURCE);
+    }
+
+    #[test]
+    fn test_change_set() {
+        let mut inc = IncrementalParser::new(parser(), SOURCE).unwrap();
+        assert_eq!(inc.document().sections.len(), 8);
+        assert_eq!(inc.chunk_starts.len(), 3);
+
+        // Typing inside a paragraph modifies only that section
+        let at = offset(&inc, "Done");
+        let changes = check(&mut inc, at..at, "All ");
+        assert_eq!(changes.modified, vec![7]);
+        assert!(changes.added.is_empty() && changes.removed.is_empty());
+
+        // Splitting a paragraph adds a section
+        let at = offset(&inc, " with");
+        let changes = check(&mut inc, at..at, "\n\nSecond");
+        assert_eq!(changes.modified, vec![1]);
+        assert_eq!(changes.added, vec![2]);
+
+        // Deleting a heading line removes it
+        let at = offset(&inc, "## Notes\n\n");
+        let changes = check(&mut inc, at..at + 10, "");
+        assert_eq!(changes.removed, vec![6]);
+        assert!(changes.modified.is_empty());
+
+        // Whitespace-only edits change nothing
+        let at = offset(&inc, "- [ ] One");
+        let changes = check(&mut inc, at..at, "\n");
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_random_ids_preserved() {
+        let mut inc = IncrementalParser::new(MarkdownParser::new(), SOURCE).unwrap();
+        let before: Vec<String> = inc
+            .document()
+            .sections
+            .iter()
+            .map(|s| s.id.clone())
+            .collect();
+
+        let at = offset(&inc, "Intro");
+        let changes = inc.edit(at..at, "\n\nFirst\n\n").unwrap();
+        assert_eq!(changes.added, vec![1]);
+
+        let after: Vec<String> = inc
+            .document()
+            .sections
+            .iter()
+            .map(|s| s.id.clone())
+            .collect();
+        assert_eq!(after[0], before[0]);
+        assert_eq!(after[2..], before[1..]);
+        assert!(!before.contains(&after[1]));
+    }
+
+    #[test]
+    fn test_invalid_edit() {
+        let mut inc = IncrementalParser::new(parser(), "Done ✓").unwrap();
+        assert!(matches!(
+            inc.edit(6..7, ""),
+            Err(ParseError::InvalidEdit(_))
+        ));
+        assert!(inc.edit(3..100, "").is_err());
+        assert_eq!(inc.source(), "Done ✓");
+        assert_eq!(inc.document().sections[0].content, "Done ✓");
+    }
+
+    #[test]
+    fn test_references_across_chunks() {
+        let parser = MarkdownParser::builder().footnotes(true).build();
+        let source = "# A\n\nSee [docs][d] and [x][] .[^n]\n\n# B\n\n[d]: https://example.com\n\
+                      [x]: https://x.example\n\n[^n]: Note.\n";
+        let mut inc = IncrementalParser::new(parser.clone(), source).unwrap();
+        let assert_matches = |inc: &IncrementalParser| {
+            let expected = parser.parse(inc.source()).unwrap();
+            let doc = inc.document();
+            let contents = |doc: &ParsedDocument| {
+                doc.sections
+                    .iter()
+                    .map(|s| s.content.clone())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(contents(doc), contents(&expected));
+            assert_eq!(doc.links, expected.links);
+            assert_eq!(doc.link_definitions, expected.link_definitions);
+            assert_eq!(doc.footnotes, expected.footnotes);
+        };
+
+        // Edits inside one chunk resolve against the other chunk's definitions
+        let at = inc.source().find("See").unwrap();
+        inc.edit(at..at + 3, "Read").unwrap();
+        assert_matches(&inc);
+        assert_eq!(inc.document().links.len(), 2);
+        assert_eq!(inc.document().links[0].url, "https://example.com");
+        assert_eq!(inc.document().sections[1].content, "Read docs and x .");
+        assert_eq!(inc.document().footnotes["n"].references.len(), 1);
+
+        // Changing or removing a definition updates references in other chunks
+        let at = inc.source().find("https://example.com").unwrap();
+        inc.edit(at..at + 19, "https://docs.example").unwrap();
+        assert_matches(&inc);
+        assert_eq!(inc.document().links[0].url, "https://docs.example");
+
+        let at = inc.source().find("[x]:").unwrap();
+        inc.edit(at..at + 23, "").unwrap();
+        assert_matches(&inc);
+        assert_eq!(inc.document().links.len(), 1);
+
+        let at = inc.source().find("[^n]:").unwrap();
+        inc.edit(at..at + 11, "").unwrap();
+        assert_matches(&inc);
+        assert!(inc.document().footnotes["n"].is_dangling());
+
+        // Adding a definition resolves an earlier reference
+        let end = inc.source().len();
+        inc.edit(end..end, "\n[x]: https://x.example\n").unwrap();
+        assert_matches(&inc);
+        assert_eq!(inc.document().links.len(), 2);
+    }
+
+    #[test]
+    fn test_footnotes_across_chunks() {
+        let parser = MarkdownParser::builder().footnotes(true).build();
+        let source = "Claim[^1]\n\n# Refs\n\n[^1]: Source.\n";
+        let mut inc = IncrementalParser::new(parser.clone(), source).unwrap();
+
+        inc.edit(0..0, "A ").unwrap();
+        let expected = parser.parse(inc.source()).unwrap();
+        assert_eq!(inc.document().footnotes, expected.footnotes);
+
+        let at = inc.source().find("Source").unwrap();
+        inc.edit(at..at + 6, "Origin").unwrap();
+        let note = &inc.document().footnotes["1"];
+        assert_eq!(note.content.as_deref(), Some("Origin."));
+        assert_eq!(note.references.len(), 1);
+    }
+}
@@ -4,9 +4,31 @@
 //!
 //! ## Features
 //!
-//! - **Core Parsing**: Parse Markdown into structured sections (heading, paragraph, list, code, blockquote, hr)
+//! - **Core Parsing**: Parse Markdown into structured sections (heading, paragraph, list, code, blockquote, hr, html, checklist, choice)
+//! - **GFM Extensions**: Opt-in tables, strikethrough, footnotes, task lists, heading attributes, smart punctuation and math
+//! - **Heading Anchors**: GitHub-style slugs with duplicate suffixes, or explicit `{#id}` with heading attributes
+//! - **Tables**: Structured headers, rows and alignments for table sections, with typed row deserialization
+//! - **Lists**: Nested item trees with ordered start numbers, marker characters and tight/loose flag
+//! - **Code Blocks**: Language, info string attributes and fenced/indented kind for code sections
+//! - **Links and Images**: URLs, titles, text and link type with reference definitions resolved
+//! - **Wiki-Links**: Obsidian `[[Page#Heading|alias]]` links and `![[embed]]`s with their sections
+//! - **Inline Fields**: Dataview `Key:: value`/`[key:: value]` and `**Key:** value` fields per section, optionally typed
+//! - **Tags and Mentions**: `#tags` (including nested `#area/sub`) and `@mentions` per section, merged with frontmatter `tags`
+//! - **Footnotes**: Definitions as their own sections, references, and dangling/unused reporting
+//! - **Raw HTML**: HTML blocks as sections, inline HTML fragments, and `<!-- key: value -->` comment annotations
+//! - **Callouts**: `> [!WARNING]` and Obsidian `> [!note]- Title` blockquotes with kind, title and fold state
+//! - **Choice Groups**: `- ( )` radio lists and `<!-- choice -->` task lists with a selection API
+//! - **Section Classifiers**: Retype, annotate, split or drop sections with a `SectionClassifier` hook
+//! - **Stable Section IDs**: Random, content-hash UUID v5, heading-slug path or custom ID strategies
+//! - **Title Sources**: Take the title from frontmatter, the first H1, the first heading or the file name
 //! - **Checklist Extraction**: Extract `- [ ]` and `- [x]` items with completion status and nesting
 //! - **Variable Detection**: Detect `{{variable_name}}` template variables
+//! - **Source Spans**: Byte offsets, line/column positions and raw Markdown for sections, checklist items and variables
+//! - **Incremental Re-parsing**: Apply text edits, re-parse only the affected heading chunks and get a change set
+//! - **Streaming**: `parse_iter`/`parse_reader` yield sections chunk by chunk with bounded memory
+//! - **Zero-Copy Parsing**: `parse_ref` returns a `ParsedDocumentRef` borrowing text from the input, with `into_owned()`
+//! - **Input Decoding**: `parse_bytes` honors BOMs, decodes UTF-16 (and legacy encodings with `encoding`) and can normalize line endings
+//! - **Batch Parsing**: `parse_many`/`parse_dir` return per-file results keyed by path, in parallel with `parallel`
 //! - **Frontmatter**: Parse YAML frontmatter (feature-gated with `frontmatter`)
 //! - **PyO3 Bindings**: Python bindings via PyO3 (feature-gated with `pyo3`)
 //! - **Serde Support**: Serialization support (feature-gated with `serde`)
@@ -44,14 +66,36 @@
 //! - `serde`: Enable serde serialization for all types
 //! - `frontmatter`: Enable YAML frontmatter parsing (requires `serde`)
 //! - `pyo3`: Enable Python bindings (requires `serde`)
+//! - `encoding`: Decode non-UTF-8 input in `parse_bytes`/`parse_file` as Windows-1252 or a chosen `fallback_encoding`
+//! - `parallel`: Parse `parse_many`/`parse_dir` batches on a rayon thread pool (ignored on WASM)
 
 // Modules
+mod anchor;
+mod callout;
 mod checklist;
+mod choice;
+mod classifier;
+mod code;
 mod document;
+mod encoding;
 mod error;
+mod fields;
+mod footnote;
+mod html;
+mod id;
+mod incremental;
+mod link;
+mod list;
+mod options;
 mod parser;
 mod section;
+mod span;
+mod stream;
+mod table;
+mod tags;
+mod title;
 mod variables;
+mod wiki;
 
 #[cfg(feature = "frontmatter")]
 pub mod frontmatter;
@@ -60,12 +104,33 @@ pub mod frontmatter;
 mod python;
 
 // Re-exports
-pub use checklist::{extract_checklist_items, ChecklistItem, ChecklistSummary};
-pub use document::{EdgeType, ParsedDocument, ParsedEdge};
+pub use anchor::slugify;
+pub use callout::{Callout, CalloutKind};
+pub use checklist::{extract_checklist_items, ChecklistItem, ChecklistItemRef, ChecklistSummary};
+pub use choice::{ChoiceGroup, ChoiceOption};
+pub use classifier::{ClassifyContext, PrefixClassifier, SectionClassifier};
+pub use code::{CodeBlockInfo, CodeBlockKind};
+pub use document::{EdgeType, ParsedDocument, ParsedDocumentRef, ParsedEdge};
 pub use error::ParseError;
-pub use parser::MarkdownParser;
-pub use section::{ParsedSection, SectionType};
-pub use variables::{count_variables, extract_unique_variables, extract_variables, has_variables};
+pub use fields::FieldValue;
+pub use footnote::{Footnote, FootnoteReference};
+pub use html::{HtmlComment, HtmlFragment};
+pub use id::{IdContext, IdFn, IdStrategy};
+pub use incremental::{ChangeSet, IncrementalParser};
+pub use link::{LinkDefinition, LinkType, ParsedLink};
+pub use list::{ListItem, ParsedList};
+pub use options::ParserOptions;
+pub use parser::{MarkdownParser, MarkdownParserBuilder};
+pub use section::{ParsedSection, ParsedSectionRef, SectionType};
+pub use span::SourceSpan;
+pub use stream::{SectionStream, StreamedSection};
+pub use table::{Alignment, ParsedTable};
+pub use title::TitleSource;
+pub use variables::{
+    count_variables, extract_unique_variables, extract_variable_occurrences, extract_variables,
+    has_variables, VariableOccurrence,
+};
+pub use wiki::WikiLink;
 
 #[cfg(feature = "frontmatter")]
 pub use frontmatter::{parse_frontmatter, strip_frontmatter};
@@ -83,6 +148,23 @@ fn md_parser(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<python::PyChecklistItem>()?;
     m.add_class::<python::PyChecklistSummary>()?;
     m.add_class::<python::PyParsedEdge>()?;
+    m.add_class::<python::PyParsedLink>()?;
+    m.add_class::<python::PyWikiLink>()?;
+    m.add_class::<python::PyFootnote>()?;
+    m.add_class::<python::PyHtmlFragment>()?;
+    m.add_class::<python::PyHtmlComment>()?;
+    m.add_class::<python::PyParsedTable>()?;
+    m.add_class::<python::PyParsedList>()?;
+    m.add_class::<python::PyListItem>()?;
+    m.add_class::<python::PyIncrementalParser>()?;
+    m.add_class::<python::PyChangeSet>()?;
+    m.add_class::<python::PySectionStream>()?;
+    m.add_class::<python::PyStreamedSection>()?;
+    m.add_class::<python::PyChoiceGroup>()?;
+    m.add_class::<python::PyCallout>()?;
+    m.add_class::<python::PyCodeBlockInfo>()?;
+    m.add_class::<python::PySourceSpan>()?;
+    m.add_class::<python::PyVariableOccurrence>()?;
 
     // Add standalone functions
     m.add_function(wrap_pyfunction!(python::py_extract_checklist_items, m)?)?;
@@ -0,0 +1,134 @@
+//! Link and image types for parsed Markdown
+
+use crate::span::SourceSpan;
+
+/// How a link or image was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinkType {
+    /// Inline link: `[text](url "title")`
+    Inline,
+    /// Reference link resolved from a definition: `[text][label]`, `[label][]` or `[label]`
+    Reference,
+    /// URL autolink: `<https://example.com>`
+    Autolink,
+    /// Email autolink: `<user@example.com>`
+    Email,
+}
+
+impl LinkType {
+    /// Get string representation of link type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkType::Inline => "inline",
+            LinkType::Reference => "reference",
+            LinkType::Autolink => "autolink",
+            LinkType::Email => "email",
+        }
+    }
+}
+
+impl std::fmt::Display for LinkType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<pulldown_cmark::LinkType> for LinkType {
+    fn from(link_type: pulldown_cmark::LinkType) -> Self {
+        match link_type {
+            pulldown_cmark::LinkType::Inline => LinkType::Inline,
+            pulldown_cmark::LinkType::Autolink => LinkType::Autolink,
+            pulldown_cmark::LinkType::Email => LinkType::Email,
+            pulldown_cmark::LinkType::Reference
+            | pulldown_cmark::LinkType::ReferenceUnknown
+            | pulldown_cmark::LinkType::Collapsed
+            | pulldown_cmark::LinkType::CollapsedUnknown
+            | pulldown_cmark::LinkType::Shortcut
+            | pulldown_cmark::LinkType::ShortcutUnknown => LinkType::Reference,
+        }
+    }
+}
+
+/// A link or image found in the document
+///
+/// Reference-style links carry the URL and title of their definition.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{LinkType, MarkdownParser};
+///
+/// let doc = MarkdownParser::new()
+///     .parse("See [the docs][docs].\n\n[docs]: https://example.com \"Docs\"")
+///     .unwrap();
+///
+/// let link = &doc.links[0];
+/// assert_eq!(link.url, "https://example.com");
+/// assert_eq!(link.title.as_deref(), Some("Docs"));
+/// assert_eq!(link.text, "the docs");
+/// assert_eq!(link.link_type, LinkType::Reference);
+/// assert_eq!(link.reference.as_deref(), Some("docs"));
+/// assert_eq!(link.section_idx, Some(0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParsedLink {
+    /// Destination URL
+    pub url: String,
+    /// Optional title
+    pub title: Option<String>,
+    /// Plain link text (alt text for images)
+    pub text: String,
+    /// How the link was written
+    pub link_type: LinkType,
+    /// Reference label for reference-style links
+    pub reference: Option<String>,
+    /// Index of the section containing the link
+    pub section_idx: Option<usize>,
+    /// Location of the full link syntax
+    pub span: SourceSpan,
+}
+
+/// A link reference definition: `[label]: url "title"`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinkDefinition {
+    /// Reference label as written
+    pub label: String,
+    /// Destination URL
+    pub url: String,
+    /// Optional title
+    pub title: Option<String>,
+    /// Location of the definition
+    pub span: SourceSpan,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_type_as_str() {
+        assert_eq!(LinkType::Inline.as_str(), "inline");
+        assert_eq!(LinkType::Reference.as_str(), "reference");
+        assert_eq!(LinkType::Autolink.as_str(), "autolink");
+        assert_eq!(LinkType::Email.as_str(), "email");
+    }
+
+    #[test]
+    fn test_link_type_from_pulldown() {
+        assert_eq!(
+            LinkType::from(pulldown_cmark::LinkType::Collapsed),
+            LinkType::Reference
+        );
+        assert_eq!(
+            LinkType::from(pulldown_cmark::LinkType::Shortcut),
+            LinkType::Reference
+        );
+        assert_eq!(
+            LinkType::from(pulldown_cmark::LinkType::Email),
+            LinkType::Email
+        );
+    }
+}
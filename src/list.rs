@@ -0,0 +1,151 @@
+//! Structured list model for list sections
+
+use crate::span::SourceSpan;
+
+/// A Markdown list with its items
+///
+/// # Example
+///
+/// ```
+/// use md_parser::MarkdownParser;
+///
+/// let doc = MarkdownParser::new()
+///     .parse("3. Setup\n4. Build\n   - debug\n   - release")
+///     .unwrap();
+///
+/// let list = doc.sections[0].list.as_ref().unwrap();
+/// assert!(list.ordered);
+/// assert_eq!(list.start, Some(3));
+/// assert_eq!(list.marker, '.');
+/// assert_eq!(list.items[1].text, "Build");
+///
+/// let nested = &list.items[1].children[0];
+/// assert_eq!(nested.marker, '-');
+/// assert_eq!(nested.items[0].text, "debug");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParsedList {
+    /// Whether the list is numbered
+    pub ordered: bool,
+    /// First number of an ordered list
+    pub start: Option<u64>,
+    /// Marker character: `-`, `*` or `+` for bullets, `.` or `)` for numbers
+    pub marker: char,
+    /// Whether the list is tight (no blank lines between items)
+    pub tight: bool,
+    /// Items in order
+    pub items: Vec<ListItem>,
+}
+
+/// A single list item
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListItem {
+    /// Plain text of the item, excluding nested lists
+    pub text: String,
+    /// Task list state (`Some(true)` for `[x]`) when task lists are enabled
+    pub checked: Option<bool>,
+    /// Lists nested under this item
+    pub children: Vec<ParsedList>,
+    /// Location of the item, including its marker and nested lists
+    pub span: SourceSpan,
+}
+
+impl ParsedList {
+    /// Create an empty list starting with the marker at the front of `source`
+    pub(crate) fn new(start: Option<u64>, source: &str) -> Self {
+        let marker = source
+            .trim_start()
+            .chars()
+            .find(|c| !c.is_ascii_digit())
+            .unwrap_or('-');
+        Self {
+            ordered: start.is_some(),
+            start,
+            marker,
+            tight: true,
+            items: Vec::new(),
+        }
+    }
+
+    /// Number of top-level items
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if the list has no items
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the number shown for the item at `idx` in an ordered list
+    pub fn number(&self, idx: usize) -> Option<u64> {
+        let start = self.start?;
+        (idx < self.items.len()).then(|| start + idx as u64)
+    }
+
+    /// Walk all items depth-first, yielding each with its nesting depth
+    pub fn walk(&self) -> Vec<(usize, &ListItem)> {
+        let mut out = Vec::new();
+        walk_into(self, 0, &mut out);
+        out
+    }
+}
+
+fn walk_into<'a>(list: &'a ParsedList, depth: usize, out: &mut Vec<(usize, &'a ListItem)>) {
+    for item in &list.items {
+        out.push((depth, item));
+        for child in &item.children {
+            walk_into(child, depth + 1, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, children: Vec<ParsedList>) -> ListItem {
+        ListItem {
+            text: text.to_string(),
+            checked: None,
+            children,
+            span: SourceSpan::default(),
+        }
+    }
+
+    #[test]
+    fn test_marker_detection() {
+        assert_eq!(ParsedList::new(None, "- a").marker, '-');
+        assert_eq!(ParsedList::new(None, "  * a").marker, '*');
+        assert_eq!(ParsedList::new(Some(10), "10) a").marker, ')');
+        assert_eq!(ParsedList::new(Some(1), "1. a").marker, '.');
+    }
+
+    #[test]
+    fn test_number() {
+        let mut list = ParsedList::new(Some(3), "3. a");
+        list.items = vec![item("a", vec![]), item("b", vec![])];
+        assert_eq!(list.number(1), Some(4));
+        assert_eq!(list.number(2), None);
+
+        let bullets = ParsedList::new(None, "- a");
+        assert_eq!(bullets.number(0), None);
+    }
+
+    #[test]
+    fn test_walk() {
+        let mut nested = ParsedList::new(None, "- x");
+        nested.items = vec![item("a.1", vec![])];
+        let mut list = ParsedList::new(None, "- a");
+        list.items = vec![item("a", vec![nested]), item("b", vec![])];
+
+        let walked: Vec<_> = list
+            .walk()
+            .into_iter()
+            .map(|(depth, item)| (depth, item.text.as_str()))
+            .collect();
+        assert_eq!(walked, vec![(0, "a"), (1, "a.1"), (0, "b")]);
+    }
+}
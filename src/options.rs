@@ -0,0 +1,118 @@
+//! Parser configuration options
+
+use pulldown_cmark::Options;
+
+/// Markdown syntax extensions honored by `MarkdownParser`
+///
+/// All extensions are disabled by default, which parses plain CommonMark.
+/// Use [`ParserOptions::gfm`] for the GitHub Flavored Markdown preset.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{MarkdownParser, ParserOptions, SectionType};
+///
+/// let parser = MarkdownParser::with_options(ParserOptions::gfm());
+/// let doc = parser.parse("| A | B |\n|---|---|\n| 1 | 2 |").unwrap();
+///
+/// assert_eq!(doc.sections[0].section_type, SectionType::Table);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParserOptions {
+    /// Pipe tables (`| a | b |`)
+    pub tables: bool,
+    /// Strikethrough (`~~text~~`)
+    pub strikethrough: bool,
+    /// Footnote references and definitions (`[^1]`)
+    pub footnotes: bool,
+    /// Task list markers (`- [ ]` / `- [x]`)
+    pub task_lists: bool,
+    /// Heading attributes (`# Heading {#id .class}`)
+    pub heading_attributes: bool,
+    /// Smart punctuation (curly quotes, dashes, ellipses)
+    pub smart_punctuation: bool,
+    /// Inline and display math (`$x$`, `$$x$$`)
+    pub math: bool,
+}
+
+impl ParserOptions {
+    /// Plain CommonMark with no extensions enabled
+    pub fn commonmark() -> Self {
+        Self::default()
+    }
+
+    /// GitHub Flavored Markdown preset
+    ///
+    /// Enables tables, strikethrough, footnotes and task lists.
+    pub fn gfm() -> Self {
+        Self {
+            tables: true,
+            strikethrough: true,
+            footnotes: true,
+            task_lists: true,
+            ..Self::default()
+        }
+    }
+
+    /// Enable every supported extension
+    pub fn all() -> Self {
+        Self {
+            tables: true,
+            strikethrough: true,
+            footnotes: true,
+            task_lists: true,
+            heading_attributes: true,
+            smart_punctuation: true,
+            math: true,
+        }
+    }
+
+    /// Convert to pulldown-cmark options
+    pub(crate) fn to_cmark(self) -> Options {
+        let mut options = Options::empty();
+        options.set(Options::ENABLE_TABLES, self.tables);
+        options.set(Options::ENABLE_STRIKETHROUGH, self.strikethrough);
+        options.set(Options::ENABLE_FOOTNOTES, self.footnotes);
+        options.set(Options::ENABLE_TASKLISTS, self.task_lists);
+        options.set(Options::ENABLE_HEADING_ATTRIBUTES, self.heading_attributes);
+        options.set(Options::ENABLE_SMART_PUNCTUATION, self.smart_punctuation);
+        options.set(Options::ENABLE_MATH, self.math);
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_commonmark() {
+        assert_eq!(ParserOptions::default(), ParserOptions::commonmark());
+        assert!(ParserOptions::default().to_cmark().is_empty());
+    }
+
+    #[test]
+    fn test_gfm_preset() {
+        let options = ParserOptions::gfm();
+        assert!(options.tables);
+        assert!(options.strikethrough);
+        assert!(options.footnotes);
+        assert!(options.task_lists);
+        assert!(!options.heading_attributes);
+        assert!(!options.smart_punctuation);
+        assert!(!options.math);
+    }
+
+    #[test]
+    fn test_to_cmark() {
+        let cmark = ParserOptions::all().to_cmark();
+        assert!(cmark.contains(Options::ENABLE_TABLES));
+        assert!(cmark.contains(Options::ENABLE_STRIKETHROUGH));
+        assert!(cmark.contains(Options::ENABLE_FOOTNOTES));
+        assert!(cmark.contains(Options::ENABLE_TASKLISTS));
+        assert!(cmark.contains(Options::ENABLE_HEADING_ATTRIBUTES));
+        assert!(cmark.contains(Options::ENABLE_SMART_PUNCTUATION));
+        assert!(cmark.contains(Options::ENABLE_MATH));
+    }
+}
@@ -1,13 +1,38 @@
 //! Markdown parser implementation
 
-use pulldown_cmark::{Event, HeadingLevel, Parser, Tag};
-use uuid::Uuid;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use crate::checklist::extract_checklist_items;
-use crate::document::{EdgeType, ParsedDocument, ParsedEdge};
+use pulldown_cmark::{BrokenLink, CowStr, Event, HeadingLevel, Parser, Tag, TagEnd};
+
+use crate::anchor::Slugger;
+use crate::callout::Callout;
+use crate::checklist::{task_item, task_marker, ChecklistItemRef};
+use crate::choice::ChoiceGroup;
+use crate::classifier::{ClassifyContext, SectionClassifier};
+use crate::code::CodeBlockInfo;
+use crate::document::{EdgeType, ParsedDocument, ParsedDocumentRef, ParsedEdge};
+use crate::encoding::{decode, normalize_line_endings};
 use crate::error::ParseError;
-use crate::section::{ParsedSection, SectionType};
-use crate::variables::extract_variables;
+use crate::fields::{fields_in, merge_fields};
+use crate::footnote::{build_footnotes, FootnoteReference};
+use crate::html::{comments_in, HtmlComment, HtmlFragment};
+use crate::id::{assign_ids, IdAssigner, IdStrategy};
+use crate::incremental::ExternalDefinitions;
+use crate::link::{LinkDefinition, LinkType, ParsedLink};
+use crate::list::{ListItem, ParsedList};
+use crate::options::ParserOptions;
+use crate::section::{ParsedSectionRef, SectionType, SectionView};
+use crate::span::{LineIndex, SourceSpan};
+use crate::stream::SectionStream;
+use crate::table::ParsedTable;
+use crate::tags::{mentions_in, tags_in};
+use crate::title::TitleSource;
+use crate::variables::{variable_names, variable_occurrences_in};
+use crate::wiki::wiki_links_in;
 
 /// Markdown to structured document parser
 ///
@@ -25,9 +50,23 @@ use crate::variables::extract_variables;
 /// assert_eq!(doc.title, Some("Hello".to_string()));
 /// assert_eq!(doc.sections.len(), 2);
 /// ```
+#[derive(Debug, Clone)]
 pub struct MarkdownParser {
-    /// Whether to generate UUIDs for section IDs
-    generate_ids: bool,
+    /// How section IDs are generated
+    id_strategy: IdStrategy,
+    /// Where to look for the document title, in order
+    title_sources: Vec<TitleSource>,
+    /// Markdown extensions to enable
+    options: ParserOptions,
+    /// Hooks consulted for each flushed section, in order
+    classifiers: Vec<Arc<dyn SectionClassifier>>,
+    /// Whether inline field values are coerced to typed values
+    coerce_fields: bool,
+    /// Whether `parse_bytes` converts line endings to `\n`
+    normalize_line_endings: bool,
+    /// Label of the encoding `parse_bytes` uses for input that is not UTF-8
+    #[cfg(feature = "encoding")]
+    fallback_encoding: Option<String>,
 }
 
 impl Default for MarkdownParser {
@@ -39,305 +78,951 @@ impl Default for MarkdownParser {
 impl MarkdownParser {
     /// Create a new parser with default settings
     pub fn new() -> Self {
-        Self { generate_ids: true }
+        Self {
+            id_strategy: IdStrategy::default(),
+            title_sources: TitleSource::defaults(),
+            options: ParserOptions::default(),
+            classifiers: Vec::new(),
+            coerce_fields: false,
+            normalize_line_endings: false,
+            #[cfg(feature = "encoding")]
+            fallback_encoding: None,
+        }
     }
 
     /// Create a parser that doesn't generate IDs (for testing)
     pub fn without_ids() -> Self {
         Self {
-            generate_ids: false,
+            id_strategy: IdStrategy::None,
+            title_sources: TitleSource::defaults(),
+            options: ParserOptions::default(),
+            classifiers: Vec::new(),
+            coerce_fields: false,
+            normalize_line_endings: false,
+            #[cfg(feature = "encoding")]
+            fallback_encoding: None,
+        }
+    }
+
+    /// Create a parser with the given Markdown extensions enabled
+    pub fn with_options(options: ParserOptions) -> Self {
+        Self {
+            id_strategy: IdStrategy::default(),
+            title_sources: TitleSource::defaults(),
+            options,
+            classifiers: Vec::new(),
+            coerce_fields: false,
+            normalize_line_endings: false,
+            #[cfg(feature = "encoding")]
+            fallback_encoding: None,
         }
     }
 
+    /// Create a builder for configuring a parser
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::MarkdownParser;
+    ///
+    /// let parser = MarkdownParser::builder().gfm().math(true).build();
+    ///
+    /// assert!(parser.options().tables);
+    /// assert!(parser.options().math);
+    /// ```
+    pub fn builder() -> MarkdownParserBuilder {
+        MarkdownParserBuilder::default()
+    }
+
+    /// Get the Markdown extensions enabled for this parser
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    /// Get the section ID strategy
+    pub fn id_strategy(&self) -> &IdStrategy {
+        &self.id_strategy
+    }
+
+    /// Get the sources tried, in order, for the document title
+    pub fn title_sources(&self) -> &[TitleSource] {
+        &self.title_sources
+    }
+
+    /// Get whether inline field values are coerced to typed values
+    pub fn coerce_fields(&self) -> bool {
+        self.coerce_fields
+    }
+
+    /// Get whether `parse_bytes` converts line endings to `\n`
+    pub fn normalize_line_endings(&self) -> bool {
+        self.normalize_line_endings
+    }
+
+    /// Get the label of the encoding used for input that is not UTF-8, if
+    /// one was set
+    #[cfg(feature = "encoding")]
+    pub fn fallback_encoding(&self) -> Option<&str> {
+        self.fallback_encoding.as_deref()
+    }
+
     /// Parse Markdown content into a structured document
     ///
     /// # Errors
     ///
     /// Returns `ParseError` if the markdown structure is invalid.
-    pub fn parse(&self, content: &str) -> Result<ParsedDocument, ParseError> {
+    pub fn parse(&self, input: &str) -> Result<ParsedDocument, ParseError> {
+        self.parse_named(input, None)
+    }
+
+    /// Parse Markdown content, using `filename` for `TitleSource::Filename`
+    fn parse_named(
+        &self,
+        input: &str,
+        filename: Option<&str>,
+    ) -> Result<ParsedDocument, ParseError> {
+        let mut doc = self.parse_without_ids(input, filename, &mut Slugger::default())?;
+        assign_ids(&mut doc.sections, &self.id_strategy);
+        Ok(doc)
+    }
+
+    /// Parse Markdown content into a document that borrows from `input`
+    ///
+    /// Gives the same result as [`parse`](Self::parse) but section text,
+    /// variable names and checklist text point into `input` instead of being
+    /// copied. See [`ParsedDocumentRef`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError` if the markdown structure is invalid.
+    pub fn parse_ref<'a>(&self, input: &'a str) -> Result<ParsedDocumentRef<'a>, ParseError> {
+        let mut doc = self.parse_ref_without_ids(input, None, &mut Slugger::default(), None)?;
+        IdAssigner::new(self.id_strategy.clone()).assign(&mut doc.sections);
+        Ok(doc)
+    }
+
+    /// Parse Markdown content, leaving section IDs empty
+    ///
+    /// Heading anchors are deduplicated against those already in `slugger`.
+    pub(crate) fn parse_without_ids(
+        &self,
+        input: &str,
+        filename: Option<&str>,
+        slugger: &mut Slugger,
+    ) -> Result<ParsedDocument, ParseError> {
+        self.parse_ref_without_ids(input, filename, slugger, None)
+            .map(ParsedDocumentRef::into_owned)
+    }
+
+    /// Parse one chunk of a larger document, leaving section IDs empty
+    ///
+    /// References to link and footnote definitions in `external` resolve as
+    /// if those definitions were part of the chunk.
+    pub(crate) fn parse_chunk(
+        &self,
+        input: &str,
+        slugger: &mut Slugger,
+        external: &ExternalDefinitions,
+    ) -> Result<ParsedDocument, ParseError> {
+        self.parse_ref_without_ids(input, None, slugger, Some(external))
+            .map(ParsedDocumentRef::into_owned)
+    }
+
+    /// Parse Markdown content into a borrowed document, leaving section IDs empty
+    fn parse_ref_without_ids<'a>(
+        &self,
+        input: &'a str,
+        filename: Option<&str>,
+        slugger: &mut Slugger,
+        external: Option<&ExternalDefinitions>,
+    ) -> Result<ParsedDocumentRef<'a>, ParseError> {
+        // A byte order mark is not part of the text
+        let body = input.strip_prefix('\u{feff}').unwrap_or(input);
+
         // Handle frontmatter if feature is enabled
         #[cfg(feature = "frontmatter")]
-        let (content, frontmatter) = crate::frontmatter::strip_frontmatter(content)?;
+        let (content, frontmatter) = crate::frontmatter::split_frontmatter(body)?;
 
         #[cfg(not(feature = "frontmatter"))]
-        let content = content;
-
-        let parser = Parser::new(&content);
-        let mut sections = Vec::new();
-        let mut current_content = String::new();
-        let mut current_type: Option<SectionType> = None;
-        let mut current_level: Option<u8> = None;
-        let mut order_idx = 0u32;
-        let mut all_variables = Vec::new();
-        let mut title = None;
+        let content = body;
+
+        // Byte offset of the Markdown body within the input
+        let base = input.len() - content.len();
+
+        let mut resolve = |link: BrokenLink<'_>| external?.resolve_link(&link.reference);
+        let mut parser = Parser::new_with_broken_link_callback(
+            content,
+            self.options.to_cmark(),
+            Some(&mut resolve),
+        )
+        .into_offset_iter();
+        let mut state = ParseState::new(input);
+        state.slugger = std::mem::take(slugger);
         let mut blockquote_depth = 0u32;
         let mut list_depth = 0u32;
+        let mut footnote_depth = 0u32;
+        // End of an external footnote reference whose text is being skipped
+        let mut external_ref_end = 0;
 
-        for event in parser {
+        for (event, range) in parser.by_ref() {
+            let range = base + range.start..base + range.end;
+            // Code blocks nested in lists and quotes are also part of their
+            // container's raw text
+            if let Event::Start(Tag::CodeBlock(_)) = event {
+                state.code_blocks.push(range.clone());
+            }
             match event {
-                Event::Start(Tag::Heading { level, .. }) => {
-                    self.flush_section(
-                        &mut sections,
-                        &mut current_content,
-                        &mut current_type,
-                        &mut current_level,
-                        &mut order_idx,
-                        &mut all_variables,
-                    );
-                    current_type = Some(SectionType::Heading);
-                    current_level = Some(heading_level_to_u8(level));
-                }
-                Event::End(pulldown_cmark::TagEnd::Heading(_)) => {
-                    // Extract title from first H1
-                    if title.is_none() && current_level == Some(1) {
-                        title = Some(current_content.trim().to_string());
-                    }
-                    self.flush_section(
-                        &mut sections,
-                        &mut current_content,
-                        &mut current_type,
-                        &mut current_level,
-                        &mut order_idx,
-                        &mut all_variables,
-                    );
+                Event::Start(Tag::Heading {
+                    level,
+                    id,
+                    classes,
+                    attrs,
+                }) => {
+                    self.flush_section(&mut state);
+                    state.begin(SectionType::Heading, range);
+                    state.level = Some(heading_level_to_u8(level));
+                    state.heading_id = id.map(|id| id.to_string());
+                    state.classes = classes.into_iter().map(|c| c.to_string()).collect();
+                    state.attributes = attrs
+                        .into_iter()
+                        .map(|(key, value)| (key.to_string(), value.map(|v| v.to_string())))
+                        .collect();
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    self.flush_section(&mut state);
                 }
+                // Don't start a new paragraph section if we're inside a blockquote or list
+                Event::Start(Tag::Paragraph)
+                    if blockquote_depth == 0 && list_depth == 0 && footnote_depth == 0 =>
+                {
+                    self.flush_section(&mut state);
+                    state.begin(SectionType::Paragraph, range);
+                }
+                // Only flush paragraph if not inside a blockquote or list
+                Event::End(TagEnd::Paragraph)
+                    if blockquote_depth == 0 && list_depth == 0 && footnote_depth == 0 =>
+                {
+                    self.flush_section(&mut state);
+                }
+                // Nested paragraphs start on a new line; inside list items
+                // they also make the list loose
                 Event::Start(Tag::Paragraph) => {
-                    // Don't start a new paragraph section if we're inside a blockquote or list
-                    if blockquote_depth == 0 && list_depth == 0 {
-                        self.flush_section(
-                            &mut sections,
-                            &mut current_content,
-                            &mut current_type,
-                            &mut current_level,
-                            &mut order_idx,
-                            &mut all_variables,
-                        );
-                        current_type = Some(SectionType::Paragraph);
+                    if !state.content.is_empty() && !state.content.ends_with('\n') {
+                        state.content.to_mut().push('\n');
                     }
-                }
-                Event::End(pulldown_cmark::TagEnd::Paragraph) => {
-                    // Only flush paragraph if not inside a blockquote or list
-                    if blockquote_depth == 0 && list_depth == 0 {
-                        self.flush_section(
-                            &mut sections,
-                            &mut current_content,
-                            &mut current_type,
-                            &mut current_level,
-                            &mut order_idx,
-                            &mut all_variables,
-                        );
+                    if let (Some(list), Some(item)) =
+                        (state.list_stack.last_mut(), state.item_stack.last_mut())
+                    {
+                        list.tight = false;
+                        if !item.text.is_empty() {
+                            item.text.push('\n');
+                        }
                     }
                 }
-                Event::Start(Tag::CodeBlock(_)) => {
-                    self.flush_section(
-                        &mut sections,
-                        &mut current_content,
-                        &mut current_type,
-                        &mut current_level,
-                        &mut order_idx,
-                        &mut all_variables,
-                    );
-                    current_type = Some(SectionType::Code);
-                }
-                Event::End(pulldown_cmark::TagEnd::CodeBlock) => {
-                    self.flush_section(
-                        &mut sections,
-                        &mut current_content,
-                        &mut current_type,
-                        &mut current_level,
-                        &mut order_idx,
-                        &mut all_variables,
-                    );
-                }
-                Event::Start(Tag::List(_)) => {
+                Event::Start(Tag::CodeBlock(kind)) if footnote_depth == 0 => {
+                    self.flush_section(&mut state);
+                    state.begin(SectionType::Code, range);
+                    state.code = Some(CodeBlockInfo::from(kind));
+                }
+                Event::End(TagEnd::CodeBlock) if footnote_depth == 0 => {
+                    self.flush_section(&mut state);
+                }
+                Event::Start(Tag::List(start)) => {
                     // Only flush and set type at the outermost list
-                    if list_depth == 0 {
-                        self.flush_section(
-                            &mut sections,
-                            &mut current_content,
-                            &mut current_type,
-                            &mut current_level,
-                            &mut order_idx,
-                            &mut all_variables,
-                        );
-                        current_type = Some(SectionType::List);
+                    if list_depth == 0 && footnote_depth == 0 {
+                        self.flush_section(&mut state);
+                        state.begin(SectionType::List, range.clone());
                     }
                     list_depth += 1;
+                    let list = ParsedList::new(start, &state.source[range.start..]);
+                    state.list_stack.push(list);
                 }
-                Event::End(pulldown_cmark::TagEnd::List(_)) => {
+                Event::End(TagEnd::List(_)) => {
+                    if let Some(list) = state.list_stack.pop() {
+                        match state.item_stack.last_mut() {
+                            Some(item) => item.children.push(list),
+                            None if state.section_type == Some(SectionType::List) => {
+                                state.list = Some(list);
+                            }
+                            None => {}
+                        }
+                    }
                     list_depth = list_depth.saturating_sub(1);
                     // Only flush at the outermost list
-                    if list_depth == 0 {
-                        self.flush_section(
-                            &mut sections,
-                            &mut current_content,
-                            &mut current_type,
-                            &mut current_level,
-                            &mut order_idx,
-                            &mut all_variables,
-                        );
+                    if list_depth == 0 && footnote_depth == 0 {
+                        self.flush_section(&mut state);
+                    }
+                }
+                Event::Start(Tag::Item) => {
+                    // Keep items on separate lines in the section content
+                    if !state.content.is_empty() && !state.content.ends_with('\n') {
+                        state.content.to_mut().push('\n');
+                    }
+                    let slice = &state.source[range.clone()];
+                    let end = range.start + slice.trim_end().len();
+                    // Without the task list extension, read the marker from the source
+                    if !self.options.task_lists {
+                        if let Some((checked, marker_end)) = task_marker(slice) {
+                            let indent = state.item_stack.len() as u32;
+                            state.checklist_items.extend(task_item(
+                                state.source,
+                                range.start,
+                                range.start + marker_end,
+                                checked,
+                                indent,
+                                &state.lines,
+                            ));
+                        }
+                    }
+                    state.item_stack.push(ListItem {
+                        text: String::new(),
+                        checked: None,
+                        children: Vec::new(),
+                        span: state.lines.span(range.start..end),
+                    });
+                }
+                Event::End(TagEnd::Item) => {
+                    if let Some(mut item) = state.item_stack.pop() {
+                        item.text = item.text.trim().to_string();
+                        if let Some(list) = state.list_stack.last_mut() {
+                            list.items.push(item);
+                        }
+                    }
+                }
+                Event::TaskListMarker(checked) => {
+                    if let Some(item) = state.item_stack.last_mut() {
+                        item.checked = Some(checked);
+                        let start = item.span.byte_range.start;
+                        let indent = state.item_stack.len() as u32 - 1;
+                        state.checklist_items.extend(task_item(
+                            state.source,
+                            start,
+                            range.end,
+                            checked,
+                            indent,
+                            &state.lines,
+                        ));
                     }
                 }
                 Event::Start(Tag::BlockQuote(_)) => {
                     // Only flush and set type at the outermost blockquote
-                    if blockquote_depth == 0 {
-                        self.flush_section(
-                            &mut sections,
-                            &mut current_content,
-                            &mut current_type,
-                            &mut current_level,
-                            &mut order_idx,
-                            &mut all_variables,
-                        );
-                        current_type = Some(SectionType::Blockquote);
+                    if blockquote_depth == 0 && footnote_depth == 0 {
+                        self.flush_section(&mut state);
+                        state.begin(SectionType::Blockquote, range);
                     }
                     blockquote_depth += 1;
                 }
-                Event::End(pulldown_cmark::TagEnd::BlockQuote(_)) => {
+                Event::End(TagEnd::BlockQuote(_)) => {
                     blockquote_depth = blockquote_depth.saturating_sub(1);
                     // Only flush at the outermost blockquote
-                    if blockquote_depth == 0 {
-                        self.flush_section(
-                            &mut sections,
-                            &mut current_content,
-                            &mut current_type,
-                            &mut current_level,
-                            &mut order_idx,
-                            &mut all_variables,
-                        );
+                    if blockquote_depth == 0 && footnote_depth == 0 {
+                        self.flush_section(&mut state);
                     }
                 }
-                Event::Start(Tag::Table(_)) => {
-                    self.flush_section(
-                        &mut sections,
-                        &mut current_content,
-                        &mut current_type,
-                        &mut current_level,
-                        &mut order_idx,
-                        &mut all_variables,
-                    );
-                    current_type = Some(SectionType::Table);
-                }
-                Event::End(pulldown_cmark::TagEnd::Table) => {
-                    self.flush_section(
-                        &mut sections,
-                        &mut current_content,
-                        &mut current_type,
-                        &mut current_level,
-                        &mut order_idx,
-                        &mut all_variables,
-                    );
+                Event::Start(Tag::FootnoteDefinition(label)) => {
+                    // Only flush and set type at the outermost definition;
+                    // inside a blockquote or list it stays part of that section
+                    if footnote_depth == 0 && blockquote_depth == 0 && list_depth == 0 {
+                        self.flush_section(&mut state);
+                        state.begin(SectionType::FootnoteDefinition, range);
+                        state.footnote_label = Some(label.to_string());
+                    } else if footnote_depth == 0 {
+                        let label_end = range.start + label.len() + 3;
+                        state.footnote_labels.push(range.start..label_end);
+                        state.nested_footnote = Some((label.to_string(), state.content.len()));
+                    }
+                    footnote_depth += 1;
                 }
-                Event::Rule => {
-                    self.flush_section(
-                        &mut sections,
-                        &mut current_content,
-                        &mut current_type,
-                        &mut current_level,
-                        &mut order_idx,
-                        &mut all_variables,
-                    );
-                    sections.push(ParsedSection {
-                        id: self.generate_id(),
-                        section_type: SectionType::HorizontalRule,
-                        level: None,
-                        content: "---".to_string(),
-                        order_idx,
-                        variables: vec![],
+                Event::End(TagEnd::FootnoteDefinition) => {
+                    footnote_depth = footnote_depth.saturating_sub(1);
+                    if footnote_depth == 0 {
+                        match state.nested_footnote.take() {
+                            Some((label, start)) => {
+                                let text = state.content[start..].trim().to_string();
+                                state.nested_footnotes.push((label, text));
+                            }
+                            None => self.flush_section(&mut state),
+                        }
+                    }
+                }
+                Event::Start(Tag::HtmlBlock) => {
+                    // Only open a section for top-level HTML blocks
+                    if blockquote_depth == 0 && list_depth == 0 && footnote_depth == 0 {
+                        self.flush_section(&mut state);
+                        state.begin(SectionType::Html, range);
+                    }
+                    state.html_block.clear();
+                    state.html_offsets.clear();
+                }
+                Event::Html(text) => {
+                    state
+                        .html_offsets
+                        .push((state.html_block.len(), range.start));
+                    state.html_block.push_str(&text);
+                    if state.section_type == Some(SectionType::Html) {
+                        state.push_content(&text);
+                    }
+                }
+                Event::End(TagEnd::HtmlBlock) => {
+                    state.push_block_comments();
+                    if state.section_type == Some(SectionType::Html) {
+                        self.flush_section(&mut state);
+                    }
+                }
+                Event::InlineHtml(text) => {
+                    if text.starts_with("<!--") {
+                        for (comment_range, comment) in comments_in(&text) {
+                            let start = range.start + comment_range.start;
+                            let end = range.start + comment_range.end;
+                            state.html_comments.push(HtmlComment {
+                                text: comment.to_string(),
+                                inline: true,
+                                section_idx: None,
+                                span: state.lines.span(start..end),
+                            });
+                        }
+                    } else {
+                        state.inline_html.push(HtmlFragment {
+                            html: text.to_string(),
+                            section_idx: None,
+                            span: state.lines.span(range),
+                        });
+                    }
+                }
+                Event::Start(Tag::Table(alignments)) => {
+                    self.flush_section(&mut state);
+                    state.begin(SectionType::Table, range);
+                    state.table = Some(ParsedTable {
+                        alignments: alignments.into_iter().map(Into::into).collect(),
+                        ..ParsedTable::default()
                     });
-                    order_idx += 1;
                 }
-                Event::Text(text) | Event::Code(text) => {
-                    current_content.push_str(&text);
+                Event::End(TagEnd::Table) => {
+                    if let Some(table) = &state.table {
+                        state.content = Cow::Owned(table.to_text());
+                    }
+                    self.flush_section(&mut state);
+                }
+                Event::Start(Tag::TableHead) => {
+                    state.in_table_head = true;
+                }
+                Event::End(TagEnd::TableHead) => {
+                    state.in_table_head = false;
+                }
+                Event::Start(Tag::TableRow) => {
+                    if let Some(table) = state.table.as_mut() {
+                        table.rows.push(Vec::new());
+                    }
+                }
+                Event::Start(Tag::TableCell) => {
+                    state.table_cell = Some(String::new());
+                }
+                Event::End(TagEnd::TableCell) => {
+                    let cell = state.table_cell.take().unwrap_or_default();
+                    let cell = cell.trim().to_string();
+                    if let Some(table) = state.table.as_mut() {
+                        if state.in_table_head {
+                            table.headers.push(cell);
+                        } else if let Some(row) = table.rows.last_mut() {
+                            row.push(cell);
+                        }
+                    }
+                }
+                Event::Start(Tag::Link {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }) => {
+                    let link = new_link(link_type, &dest_url, &title, &id, &state, range);
+                    state.open_links.push((false, link));
+                }
+                Event::Start(Tag::Image {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }) => {
+                    let image = new_link(link_type, &dest_url, &title, &id, &state, range);
+                    state.open_links.push((true, image));
+                }
+                Event::End(TagEnd::Link) | Event::End(TagEnd::Image) => {
+                    match state.open_links.pop() {
+                        Some((true, image)) => state.images.push(image),
+                        Some((false, link)) => state.links.push(link),
+                        None => {}
+                    }
+                }
+                Event::Rule => {
+                    self.flush_section(&mut state);
+                    state.begin(SectionType::HorizontalRule, range);
+                    state.content = Cow::Borrowed("---");
+                    self.flush_section(&mut state);
+                }
+                Event::Code(text) => {
+                    state.push_text(&text);
+                    state.code_ranges.push(range);
+                }
+                Event::Text(_) if range.end <= external_ref_end => {}
+                Event::Text(text) => {
+                    // Without its definition, `[^label]` is left as text
+                    let footnote = (&*text == "[")
+                        .then(|| external?.footnote_at(&input[range.start..]))
+                        .flatten();
+                    match footnote {
+                        Some((label, len)) => {
+                            external_ref_end = range.start + len;
+                            let reference = FootnoteReference {
+                                section_idx: None,
+                                span: state.lines.span(range.start..external_ref_end),
+                            };
+                            state.footnote_refs.push((label.to_string(), reference));
+                        }
+                        None => state.push_text(&text),
+                    }
+                }
+                Event::InlineMath(text) | Event::DisplayMath(text) => {
+                    state.push_text(&text);
+                }
+                Event::FootnoteReference(label) => {
+                    let reference = FootnoteReference {
+                        section_idx: None,
+                        span: state.lines.span(range),
+                    };
+                    state.footnote_refs.push((label.to_string(), reference));
                 }
                 Event::SoftBreak | Event::HardBreak => {
-                    current_content.push('\n');
+                    state.content.to_mut().push('\n');
+                    if let Some(item) = state.item_stack.last_mut() {
+                        item.text.push('\n');
+                    }
                 }
                 _ => {}
             }
         }
 
         // Flush any remaining content
-        self.flush_section(
-            &mut sections,
-            &mut current_content,
-            &mut current_type,
-            &mut current_level,
-            &mut order_idx,
-            &mut all_variables,
-        );
+        self.flush_section(&mut state);
+        *slugger = std::mem::take(&mut state.slugger);
+
+        // Collect link reference definitions in document order
+        let mut link_definitions: Vec<LinkDefinition> = parser
+            .reference_definitions()
+            .iter()
+            .map(|(label, def)| LinkDefinition {
+                label: label.to_string(),
+                url: def.dest.to_string(),
+                title: def.title.as_ref().map(|t| t.to_string()),
+                span: state.lines.span(base + def.span.start..base + def.span.end),
+            })
+            .collect();
+        link_definitions.sort_by_key(|d| d.span.byte_range.start);
+
+        let footnotes = if self.options.footnotes {
+            build_footnotes(
+                &state.sections,
+                &state.footnote_definitions,
+                std::mem::take(&mut state.footnote_refs),
+                &[
+                    state.code_ranges.as_slice(),
+                    &state.code_blocks,
+                    &state.footnote_labels,
+                ]
+                .concat(),
+                &state.lines,
+            )
+        } else {
+            Default::default()
+        };
+
+        // Wiki-links, tags, mentions and fields are never found in code or inline HTML
+        let code_and_html: Vec<Range<usize>> = state
+            .code_ranges
+            .iter()
+            .chain(&state.code_blocks)
+            .cloned()
+            .chain(state.inline_html.iter().map(|h| h.span.byte_range.clone()))
+            .collect();
+        let wiki_links = wiki_links_in(&state.sections, &code_and_html, &state.lines);
+
+        // Tags and mentions are also not part of links
+        let skip: Vec<Range<usize>> = code_and_html
+            .iter()
+            .cloned()
+            .chain(state.links.iter().map(|l| l.span.byte_range.clone()))
+            .chain(state.images.iter().map(|l| l.span.byte_range.clone()))
+            .chain(wiki_links.iter().map(|l| l.span.byte_range.clone()))
+            .collect();
+        for section in &mut state.sections {
+            if matches!(section.section_type, SectionType::Code | SectionType::Html) {
+                continue;
+            }
+            let (raw, start) = (section.raw, section.span.byte_range.start);
+            section.tags = tags_in(raw, start, &skip);
+            section.mentions = mentions_in(raw, start, &skip);
+            section.fields = fields_in(raw, start, &code_and_html, self.coerce_fields);
+        }
 
         // Generate edges (sequential follows relationships)
-        let edges = self.generate_edges(&sections);
+        let edges = self.generate_edges(&state.sections);
 
         // Deduplicate and sort variables
+        let mut all_variables = state.variables;
         all_variables.sort();
         all_variables.dedup();
 
         // Extract checklist items from original content
-        let checklist_items = extract_checklist_items(&content);
+        let mut checklist_items = std::mem::take(&mut state.checklist_items);
+        link_checklist_items(&mut state.sections, &mut checklist_items);
+        detect_choices(&mut state.sections, &state.html_comments);
+        // Options of a choice group are not tasks
+        checklist_items.retain(|item| {
+            item.section_idx
+                .is_none_or(|idx| state.sections[idx].section_type != SectionType::Choice)
+        });
 
-        Ok(ParsedDocument {
-            title,
-            sections,
+        let mut doc = ParsedDocumentRef {
+            title: None,
+            title_source: None,
+            sections: state.sections,
             variables: all_variables,
             edges,
             checklist_items,
+            links: state.links,
+            images: state.images,
+            link_definitions,
+            wiki_links,
+            tags: BTreeSet::new(),
+            mentions: BTreeSet::new(),
+            fields: BTreeMap::new(),
+            footnotes,
+            inline_html: state.inline_html,
+            html_comments: state.html_comments,
             #[cfg(feature = "frontmatter")]
             frontmatter,
+        };
+        (doc.title, doc.title_source) = self
+            .resolve_title(&doc.sections, |key| doc.frontmatter_string(key), filename)
+            .unzip();
+        doc.tags = doc.frontmatter_tags().into_iter().collect();
+        doc.tags.extend(
+            doc.sections
+                .iter()
+                .flat_map(|s| &s.tags)
+                .map(|t| t.to_string()),
+        );
+        doc.mentions = doc
+            .sections
+            .iter()
+            .flat_map(|s| &s.mentions)
+            .map(|m| m.to_string())
+            .collect();
+        doc.fields = merge_fields(doc.sections.iter().map(|s| &s.fields));
+        Ok(doc)
+    }
+
+    /// Find the title from the first configured source that has one
+    pub(crate) fn resolve_title<S: SectionView>(
+        &self,
+        sections: &[S],
+        frontmatter: impl Fn(&str) -> Option<String>,
+        filename: Option<&str>,
+    ) -> Option<(String, TitleSource)> {
+        let mut headings = sections
+            .iter()
+            .filter(|s| s.section_type() == SectionType::Heading);
+        let first_heading = headings.clone().next();
+        let first_h1 = headings.find(|s| s.level() == Some(1));
+
+        self.title_sources.iter().find_map(|source| {
+            let title = match source {
+                TitleSource::Frontmatter(key) => frontmatter(key),
+                TitleSource::FirstH1 => first_h1.map(|s| s.content().to_string()),
+                TitleSource::FirstHeading => first_heading.map(|s| s.content().to_string()),
+                TitleSource::Filename => filename.map(String::from),
+            };
+            title.filter(|t| !t.is_empty()).map(|t| (t, source.clone()))
         })
     }
 
+    /// Stream the sections of Markdown content one chunk at a time
+    ///
+    /// See [`SectionStream`] for how the input is split.
+    pub fn parse_iter<'a>(&self, input: &'a str) -> SectionStream<&'a [u8]> {
+        self.parse_reader(input.as_bytes())
+    }
+
+    /// Stream the sections of Markdown read from `reader`
+    ///
+    /// Only one chunk of input is held in memory at a time. Read errors,
+    /// including invalid UTF-8, are yielded as `ParseError::IoError` and end
+    /// the stream.
+    pub fn parse_reader<R: std::io::BufRead>(&self, reader: R) -> SectionStream<R> {
+        SectionStream::new(self.clone(), reader)
+    }
+
+    /// Parse raw Markdown bytes, decoding them first
+    ///
+    /// A UTF-8 or UTF-16 byte order mark selects the encoding. Input without
+    /// a BOM must be UTF-8 unless the `encoding` feature is enabled, in which
+    /// case it falls back to Windows-1252 or the encoding set with
+    /// [`fallback_encoding`](MarkdownParserBuilder::fallback_encoding). The
+    /// encoding used is recorded in `ParsedDocument::encoding`.
+    ///
+    /// Source spans are byte offsets into the UTF-8 input as given, BOM and
+    /// `\r\n` line endings included, unless the parser was built with
+    /// [`normalize_line_endings`](MarkdownParserBuilder::normalize_line_endings).
+    /// Spans of UTF-16 input refer to the decoded UTF-8 text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::MarkdownParser;
+    ///
+    /// let bytes = b"\xEF\xBB\xBF# Title\r\n\r\n- [ ] Task\r\n";
+    /// let doc = MarkdownParser::new().parse_bytes(bytes).unwrap();
+    ///
+    /// assert_eq!(doc.title.as_deref(), Some("Title"));
+    /// assert_eq!(doc.checklist_items[0].text, "Task");
+    /// assert_eq!(doc.checklist_items[0].span.byte_range, 14..24);
+    /// assert_eq!(doc.encoding.as_deref(), Some("UTF-8"));
+    ///
+    /// let parser = MarkdownParser::builder().normalize_line_endings(true).build();
+    /// let doc = parser.parse_bytes(bytes).unwrap();
+    /// assert_eq!(doc.checklist_items[0].span.byte_range, 12..22);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidEncoding` if the bytes cannot be decoded,
+    /// or other `ParseError` variants if parsing fails.
+    pub fn parse_bytes(&self, bytes: &[u8]) -> Result<ParsedDocument, ParseError> {
+        self.parse_bytes_named(bytes, None)
+    }
+
+    /// Parse raw Markdown bytes, using `filename` for `TitleSource::Filename`
+    fn parse_bytes_named(
+        &self,
+        bytes: &[u8],
+        filename: Option<&str>,
+    ) -> Result<ParsedDocument, ParseError> {
+        #[cfg(feature = "encoding")]
+        let fallback = self.fallback_encoding.as_deref();
+        #[cfg(not(feature = "encoding"))]
+        let fallback = None;
+        let (text, encoding) = decode(bytes, fallback)?;
+        let mut doc = if self.normalize_line_endings {
+            self.parse_named(&normalize_line_endings(&text), filename)?
+        } else {
+            self.parse_named(&text, filename)?
+        };
+        doc.encoding = Some(encoding.to_string());
+        Ok(doc)
+    }
+
     /// Parse Markdown from a file
     ///
+    /// The file is decoded like [`parse_bytes`](Self::parse_bytes),
+    /// and its name (without extension) is used for `TitleSource::Filename`.
+    ///
     /// # Errors
     ///
     /// Returns `ParseError::IoError` if the file cannot be read,
+    /// `ParseError::InvalidEncoding` if it cannot be decoded,
     /// or other `ParseError` variants if parsing fails.
     pub fn parse_file(&self, path: &std::path::Path) -> Result<ParsedDocument, ParseError> {
-        let content = std::fs::read_to_string(path)?;
-        self.parse(&content)
+        let bytes = std::fs::read(path)?;
+        let stem = path.file_stem().map(|s| s.to_string_lossy());
+        self.parse_bytes_named(&bytes, stem.as_deref())
     }
 
-    fn flush_section(
+    /// Parse many Markdown files, returning each file's result keyed by path
+    ///
+    /// With the `parallel` feature the files are parsed on rayon's global
+    /// thread pool; without it, and always on WASM, they are parsed one after
+    /// another.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use md_parser::MarkdownParser;
+    ///
+    /// let results = MarkdownParser::new().parse_many(["README.md", "docs/intro.md"]);
+    /// for (path, result) in &results {
+    ///     match result {
+    ///         Ok(doc) => println!("{}: {} sections", path.display(), doc.sections.len()),
+    ///         Err(e) => eprintln!("{}: {}", path.display(), e),
+    ///     }
+    /// }
+    /// ```
+    pub fn parse_many<I, P>(
         &self,
-        sections: &mut Vec<ParsedSection>,
-        content: &mut String,
-        section_type: &mut Option<SectionType>,
-        level: &mut Option<u8>,
-        order_idx: &mut u32,
-        all_variables: &mut Vec<String>,
-    ) {
-        if let Some(st) = section_type.take() {
-            let trimmed = content.trim();
-            if !trimmed.is_empty() {
-                let variables = extract_variables(trimmed);
-                all_variables.extend(variables.clone());
-
-                sections.push(ParsedSection {
-                    id: self.generate_id(),
-                    section_type: st,
-                    level: level.take(),
-                    content: trimmed.to_string(),
-                    order_idx: *order_idx,
-                    variables,
-                });
-                *order_idx += 1;
+        paths: I,
+    ) -> BTreeMap<PathBuf, Result<ParsedDocument, ParseError>>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .map(|path| path.as_ref().to_path_buf())
+            .collect();
+
+        #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+        {
+            use rayon::prelude::*;
+            paths
+                .into_par_iter()
+                .map(|path| {
+                    let result = self.parse_file(&path);
+                    (path, result)
+                })
+                .collect()
+        }
+        #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+        {
+            paths
+                .into_iter()
+                .map(|path| {
+                    let result = self.parse_file(&path);
+                    (path, result)
+                })
+                .collect()
+        }
+    }
+
+    /// Parse every file under `root` whose path matches a glob pattern
+    ///
+    /// The pattern is relative to `root`, e.g. `**/*.md`. Directories are
+    /// skipped, and entries that cannot be read are reported in the map as
+    /// `ParseError::IoError`. See [`parse_many`](Self::parse_many).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::InvalidPattern` if the pattern is malformed.
+    pub fn parse_dir(
+        &self,
+        root: &Path,
+        pattern: &str,
+    ) -> Result<BTreeMap<PathBuf, Result<ParsedDocument, ParseError>>, ParseError> {
+        let root = glob::Pattern::escape(&root.to_string_lossy());
+        let pattern = format!("{}/{}", root.trim_end_matches('/'), pattern);
+        let entries =
+            glob::glob(&pattern).map_err(|e| ParseError::InvalidPattern(e.to_string()))?;
+
+        let mut paths = Vec::new();
+        let mut unreadable = Vec::new();
+        for entry in entries {
+            match entry {
+                Ok(path) if path.is_file() => paths.push(path),
+                Ok(_) => {}
+                Err(e) => {
+                    let path = e.path().to_path_buf();
+                    unreadable.push((path, std::io::Error::from(e).into()));
+                }
             }
         }
-        content.clear();
-        *level = None;
+
+        let mut results = self.parse_many(paths);
+        for (path, error) in unreadable {
+            results.insert(path, Err(error));
+        }
+        Ok(results)
     }
 
-    fn generate_id(&self) -> String {
-        if self.generate_ids {
-            Uuid::new_v4().to_string()
-        } else {
-            String::new()
+    fn flush_section<'a>(&self, state: &mut ParseState<'a>) {
+        let first = state.sections.len();
+        if let Some(st) = state.section_type.take() {
+            let content = trim_cow(std::mem::take(&mut state.content));
+            if !content.is_empty() {
+                let variables: Vec<Cow<'a, str>> = match &content {
+                    Cow::Borrowed(text) => variable_names(text).map(Cow::Borrowed).collect(),
+                    Cow::Owned(text) => variable_names(text)
+                        .map(|name| Cow::Owned(name.to_string()))
+                        .collect(),
+                };
+
+                let range = state.trimmed_range();
+                let raw = &state.source[range.clone()];
+                let variable_occurrences = variable_occurrences_in(raw, range.start, &state.lines);
+
+                let anchor = (st == SectionType::Heading).then(|| match state.heading_id.take() {
+                    Some(id) => {
+                        state.slugger.reserve(&id);
+                        id
+                    }
+                    None => state.slugger.slug(&content),
+                });
+                let callout = (st == SectionType::Blockquote)
+                    .then(|| Callout::parse(&content))
+                    .flatten();
+
+                let section = ParsedSectionRef {
+                    id: String::new(),
+                    section_type: st,
+                    level: state.level.take(),
+                    content,
+                    raw,
+                    order_idx: 0,
+                    variables,
+                    span: state.lines.span(range),
+                    variable_occurrences,
+                    table: state.table.take(),
+                    code: state.code.take(),
+                    list: state.list.take(),
+                    choice: None,
+                    anchor,
+                    classes: std::mem::take(&mut state.classes),
+                    attributes: std::mem::take(&mut state.attributes),
+                    callout,
+                    custom_type: None,
+                    annotations: BTreeMap::new(),
+                    tags: Vec::new(),
+                    mentions: Vec::new(),
+                    fields: BTreeMap::new(),
+                };
+
+                if self.classifiers.is_empty() {
+                    state.push_section(section);
+                } else {
+                    let ctx = ClassifyContext::new(state.source, &state.lines);
+                    let mut parts = vec![section];
+                    for classifier in &self.classifiers {
+                        parts = parts
+                            .into_iter()
+                            .flat_map(|part| classifier.classify(part, &ctx))
+                            .collect();
+                    }
+                    for part in parts {
+                        state.push_section(part);
+                    }
+                }
+            }
+        }
+        let owners = first..state.sections.len();
+        if let Some(label) = state.footnote_label.take() {
+            if !owners.is_empty() {
+                let content = state.sections[first].content.to_string();
+                state.footnote_definitions.push((label, first, content));
+            }
+        }
+        if !owners.is_empty() {
+            for (label, content) in std::mem::take(&mut state.nested_footnotes) {
+                state.footnote_definitions.push((label, first, content));
+            }
         }
+        state.assign_owners(owners);
+        state.content = Cow::Borrowed("");
+        state.level = None;
+        state.table = None;
+        state.code = None;
+        state.list = None;
+        state.heading_id = None;
+        state.classes.clear();
+        state.attributes.clear();
     }
 
-    fn generate_edges(&self, sections: &[ParsedSection]) -> Vec<ParsedEdge> {
+    pub(crate) fn generate_edges<S: SectionView>(&self, sections: &[S]) -> Vec<ParsedEdge> {
         let mut edges = Vec::new();
 
         // Create "follows" edges between sequential sections
@@ -349,88 +1034,683 @@ impl MarkdownParser {
             });
         }
 
+        // Create "contains" edges from each heading to the sections it owns
+        for (idx, parent) in heading_parents(sections).into_iter().enumerate() {
+            if let Some(parent) = parent {
+                edges.push(ParsedEdge::contains(parent, idx));
+            }
+        }
+
         edges
     }
 }
 
-/// Convert pulldown-cmark HeadingLevel to u8
-fn heading_level_to_u8(level: HeadingLevel) -> u8 {
-    match level {
-        HeadingLevel::H1 => 1,
-        HeadingLevel::H2 => 2,
-        HeadingLevel::H3 => 3,
-        HeadingLevel::H4 => 4,
-        HeadingLevel::H5 => 5,
-        HeadingLevel::H6 => 6,
+/// Find the owning heading of every section
+///
+/// A heading owns everything up to the next heading of the same or higher
+/// level; nested headings are owned by the closest lower-level heading.
+fn heading_parents<S: SectionView>(sections: &[S]) -> Vec<Option<usize>> {
+    // Stack of (section index, heading level) for the currently open headings
+    let mut open: Vec<(usize, u8)> = Vec::new();
+    let mut parents = Vec::with_capacity(sections.len());
+
+    for (idx, section) in sections.iter().enumerate() {
+        if section.section_type() == SectionType::Heading {
+            let level = section.level().unwrap_or(1);
+            while open
+                .last()
+                .is_some_and(|&(_, open_level)| open_level >= level)
+            {
+                open.pop();
+            }
+            parents.push(open.last().map(|&(parent, _)| parent));
+            open.push((idx, level));
+        } else {
+            parents.push(open.last().map(|&(parent, _)| parent));
+        }
     }
+
+    parents
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Set the owning section of each checklist item and mark lists made up
+/// entirely of task items as checklists
+fn link_checklist_items(sections: &mut [ParsedSectionRef<'_>], items: &mut [ChecklistItemRef<'_>]) {
+    for item in items.iter_mut() {
+        let start = item.span.byte_range.start;
+        let idx = sections.partition_point(|s| s.span.byte_range.start <= start);
+        item.section_idx = idx.checked_sub(1).filter(|&idx| {
+            sections[idx].span.contains(start) && sections[idx].section_type != SectionType::Code
+        });
+    }
 
-    #[test]
-    fn test_parse_simple() {
-        let parser = MarkdownParser::new();
-        let doc = parser.parse("# Hello\n\nWorld").unwrap();
+    let task_starts: HashSet<usize> = items.iter().map(|i| i.span.byte_range.start).collect();
+    for section in sections.iter_mut() {
+        let Some(list) = section.list.as_ref() else {
+            continue;
+        };
+        let all_tasks = !list.is_empty()
+            && list
+                .items
+                .iter()
+                .all(|list_item| task_starts.contains(&list_item.span.byte_range.start));
+        if section.section_type == SectionType::List && all_tasks {
+            section.section_type = SectionType::Checklist;
+        }
+    }
+}
 
-        assert_eq!(doc.sections.len(), 2);
-        assert_eq!(doc.sections[0].section_type, SectionType::Heading);
-        assert_eq!(doc.sections[0].level, Some(1));
-        assert_eq!(doc.sections[1].section_type, SectionType::Paragraph);
+/// Turn radio lists, and lists after a `<!-- choice -->` directive, into
+/// choice sections
+fn detect_choices(sections: &mut [ParsedSectionRef<'_>], comments: &[HtmlComment]) {
+    for idx in 0..sections.len() {
+        let directive = idx
+            .checked_sub(1)
+            .filter(|&prev| sections[prev].section_type == SectionType::Html)
+            .and_then(|prev| {
+                comments
+                    .iter()
+                    .filter(|c| c.section_idx == Some(prev) && !c.inline)
+                    .find_map(|c| match c.annotation() {
+                        Some(("choice", value)) => Some(value),
+                        _ => (c.text == "choice").then_some(""),
+                    })
+            });
+        let section = &mut sections[idx];
+        if !matches!(
+            section.section_type,
+            SectionType::List | SectionType::Checklist
+        ) {
+            continue;
+        }
+        if let Some(choice) = section
+            .list
+            .as_ref()
+            .and_then(|list| ChoiceGroup::from_list(list, directive))
+        {
+            section.section_type = SectionType::Choice;
+            section.choice = Some(choice);
+        }
     }
+}
 
-    #[test]
-    fn test_parse_code_block() {
-        let parser = MarkdownParser::new();
-        let doc = parser.parse("```rust\nfn main() {}\n```").unwrap();
+/// Builder for configuring a `MarkdownParser`
+///
+/// Created with [`MarkdownParser::builder`].
+#[derive(Debug, Clone)]
+pub struct MarkdownParserBuilder {
+    id_strategy: IdStrategy,
+    title_sources: Vec<TitleSource>,
+    options: ParserOptions,
+    classifiers: Vec<Arc<dyn SectionClassifier>>,
+    coerce_fields: bool,
+    normalize_line_endings: bool,
+    #[cfg(feature = "encoding")]
+    fallback_encoding: Option<String>,
+}
 
-        assert_eq!(doc.sections.len(), 1);
-        assert_eq!(doc.sections[0].section_type, SectionType::Code);
+impl Default for MarkdownParserBuilder {
+    fn default() -> Self {
+        Self {
+            id_strategy: IdStrategy::default(),
+            title_sources: TitleSource::defaults(),
+            options: ParserOptions::default(),
+            classifiers: Vec::new(),
+            coerce_fields: false,
+            normalize_line_endings: false,
+            #[cfg(feature = "encoding")]
+            fallback_encoding: None,
+        }
     }
+}
 
-    #[test]
-    fn test_parse_list() {
-        let parser = MarkdownParser::new();
-        let doc = parser.parse("- Item 1\n- Item 2\n- Item 3").unwrap();
+impl MarkdownParserBuilder {
+    /// Replace all Markdown extension options at once
+    pub fn options(mut self, options: ParserOptions) -> Self {
+        self.options = options;
+        self
+    }
 
-        assert_eq!(doc.sections.len(), 1);
-        assert_eq!(doc.sections[0].section_type, SectionType::List);
+    /// Enable the GitHub Flavored Markdown preset
+    pub fn gfm(self) -> Self {
+        self.options(ParserOptions::gfm())
     }
 
-    #[test]
-    fn test_generate_edges() {
-        let parser = MarkdownParser::new();
-        let doc = parser.parse("# A\n\nB\n\nC").unwrap();
+    /// Enable or disable pipe tables
+    pub fn tables(mut self, enabled: bool) -> Self {
+        self.options.tables = enabled;
+        self
+    }
 
-        assert_eq!(doc.edges.len(), 2); // A->B, B->C
+    /// Enable or disable strikethrough
+    pub fn strikethrough(mut self, enabled: bool) -> Self {
+        self.options.strikethrough = enabled;
+        self
     }
 
-    #[test]
-    fn test_extract_title() {
-        let parser = MarkdownParser::new();
-        let doc = parser.parse("# My Document\n\nContent here").unwrap();
+    /// Enable or disable footnotes
+    pub fn footnotes(mut self, enabled: bool) -> Self {
+        self.options.footnotes = enabled;
+        self
+    }
 
-        assert_eq!(doc.title, Some("My Document".to_string()));
+    /// Enable or disable task list markers
+    pub fn task_lists(mut self, enabled: bool) -> Self {
+        self.options.task_lists = enabled;
+        self
     }
 
-    #[test]
-    fn test_parse_headers_h1_to_h6() {
-        let parser = MarkdownParser::new();
-        let markdown = "# H1\n\n## H2\n\n### H3\n\n#### H4\n\n##### H5\n\n###### H6";
-        let doc = parser.parse(markdown).unwrap();
+    /// Enable or disable heading attributes
+    pub fn heading_attributes(mut self, enabled: bool) -> Self {
+        self.options.heading_attributes = enabled;
+        self
+    }
 
-        assert_eq!(doc.sections.len(), 6);
-        for (i, section) in doc.sections.iter().enumerate() {
-            assert_eq!(section.section_type, SectionType::Heading);
-            assert_eq!(section.level, Some((i + 1) as u8));
-        }
+    /// Enable or disable smart punctuation
+    pub fn smart_punctuation(mut self, enabled: bool) -> Self {
+        self.options.smart_punctuation = enabled;
+        self
     }
 
-    #[test]
-    fn test_parse_blockquote() {
-        let parser = MarkdownParser::new();
-        let doc = parser.parse("> This is a quote").unwrap();
+    /// Enable or disable math
+    pub fn math(mut self, enabled: bool) -> Self {
+        self.options.math = enabled;
+        self
+    }
+
+    /// Set whether random UUIDs are generated for section IDs
+    ///
+    /// Shorthand for `IdStrategy::Random` or `IdStrategy::None`.
+    pub fn generate_ids(mut self, enabled: bool) -> Self {
+        self.id_strategy = if enabled {
+            IdStrategy::Random
+        } else {
+            IdStrategy::None
+        };
+        self
+    }
+
+    /// Set how section IDs are generated
+    pub fn id_strategy(mut self, strategy: IdStrategy) -> Self {
+        self.id_strategy = strategy;
+        self
+    }
+
+    /// Set where to look for the document title, tried in order
+    ///
+    /// The default is `[TitleSource::FirstH1]`.
+    pub fn title_sources(mut self, sources: Vec<TitleSource>) -> Self {
+        self.title_sources = sources;
+        self
+    }
+
+    /// Add a hook that can retype, annotate, split or drop sections
+    ///
+    /// Classifiers run in the order they are added. See [`SectionClassifier`].
+    pub fn classifier(mut self, classifier: impl SectionClassifier + 'static) -> Self {
+        self.classifiers.push(Arc::new(classifier));
+        self
+    }
+
+    /// Set whether inline field values are coerced to numbers, booleans,
+    /// dates and lists
+    ///
+    /// Off by default, which keeps every value as [`FieldValue::Text`](crate::FieldValue::Text).
+    pub fn coerce_fields(mut self, enabled: bool) -> Self {
+        self.coerce_fields = enabled;
+        self
+    }
+
+    /// Set whether `parse_bytes` and `parse_file` convert `\r\n` and `\r`
+    /// line endings to `\n`
+    ///
+    /// Off by default, so source spans are byte offsets into the file as
+    /// read. Turn it on to get `\n`-only text, at the cost of spans that
+    /// refer to the normalized text instead. Lone `\r` line endings only
+    /// count as line breaks in `SourceSpan` line numbers when normalized.
+    pub fn normalize_line_endings(mut self, enabled: bool) -> Self {
+        self.normalize_line_endings = enabled;
+        self
+    }
+
+    /// Set the encoding `parse_bytes` and `parse_file` use for input that is
+    /// neither valid UTF-8 nor marked with a BOM
+    ///
+    /// `label` is a WHATWG encoding label such as `"latin1"`, `"cp1251"` or
+    /// `"shift_jis"`. The default is Windows-1252. An unknown label makes
+    /// `parse_bytes` return `ParseError::InvalidEncoding`.
+    #[cfg(feature = "encoding")]
+    pub fn fallback_encoding(mut self, label: impl Into<String>) -> Self {
+        self.fallback_encoding = Some(label.into());
+        self
+    }
+
+    /// Build the configured parser
+    pub fn build(self) -> MarkdownParser {
+        MarkdownParser {
+            id_strategy: self.id_strategy,
+            title_sources: self.title_sources,
+            options: self.options,
+            classifiers: self.classifiers,
+            coerce_fields: self.coerce_fields,
+            normalize_line_endings: self.normalize_line_endings,
+            #[cfg(feature = "encoding")]
+            fallback_encoding: self.fallback_encoding,
+        }
+    }
+}
+
+/// Mutable state threaded through the parse loop
+struct ParseState<'a> {
+    /// Full input text (including frontmatter)
+    source: &'a str,
+    /// Line index over the full input
+    lines: LineIndex<'a>,
+    /// Sections flushed so far
+    sections: Vec<ParsedSectionRef<'a>>,
+    /// Text accumulated for the current section, borrowed while it is a
+    /// single run of the source
+    content: Cow<'a, str>,
+    /// Type of the current section, if one is open
+    section_type: Option<SectionType>,
+    /// Heading level of the current section
+    level: Option<u8>,
+    /// Byte range of the current section in `source`
+    range: Range<usize>,
+    /// Order index for the next flushed section
+    order_idx: u32,
+    /// All variable names found so far (with duplicates)
+    variables: Vec<Cow<'a, str>>,
+    /// Table being built for the current table section
+    table: Option<ParsedTable>,
+    /// Text of the table cell being read
+    table_cell: Option<String>,
+    /// Whether the parser is inside the table header row
+    in_table_head: bool,
+    /// Metadata for the current code section
+    code: Option<CodeBlockInfo>,
+    /// Explicit `{#id}` of the current heading
+    heading_id: Option<String>,
+    /// `{.class}` names of the current heading
+    classes: Vec<String>,
+    /// `{key=value}` attributes of the current heading
+    attributes: BTreeMap<String, Option<String>>,
+    /// Generates unique heading anchors
+    slugger: Slugger,
+    /// Item tree for the current list section
+    list: Option<ParsedList>,
+    /// Lists still being read, innermost last
+    list_stack: Vec<ParsedList>,
+    /// List items still being read, innermost last
+    item_stack: Vec<ListItem>,
+    /// Task list items in document order
+    checklist_items: Vec<ChecklistItemRef<'a>>,
+    /// Links found so far
+    links: Vec<ParsedLink>,
+    /// Images found so far
+    images: Vec<ParsedLink>,
+    /// Links and images still being read, innermost last (`true` for images)
+    open_links: Vec<(bool, ParsedLink)>,
+    /// Number of links already assigned to a section
+    links_assigned: usize,
+    /// Number of images already assigned to a section
+    images_assigned: usize,
+    /// Label of the current footnote definition section
+    footnote_label: Option<String>,
+    /// Footnote definitions found so far as (label, section index, content)
+    footnote_definitions: Vec<(String, usize, String)>,
+    /// Label and start in `content` of the footnote definition being read
+    /// inside a blockquote or list
+    nested_footnote: Option<(String, usize)>,
+    /// Label and content of definitions in the current blockquote or list
+    nested_footnotes: Vec<(String, String)>,
+    /// Byte ranges of the `[^label]` of definitions inside blockquotes and lists
+    footnote_labels: Vec<Range<usize>>,
+    /// Footnote references found so far
+    footnote_refs: Vec<(String, FootnoteReference)>,
+    /// Number of footnote references already assigned to a section
+    footnote_refs_assigned: usize,
+    /// Byte ranges of inline code spans
+    code_ranges: Vec<Range<usize>>,
+    /// Byte ranges of code blocks, including nested ones
+    code_blocks: Vec<Range<usize>>,
+    /// Text of the HTML block being read
+    html_block: String,
+    /// (offset in `html_block`, offset in `source`) for each HTML line
+    html_offsets: Vec<(usize, usize)>,
+    /// Inline HTML fragments found so far
+    inline_html: Vec<HtmlFragment>,
+    /// Number of inline HTML fragments already assigned to a section
+    inline_html_assigned: usize,
+    /// HTML comments found so far
+    html_comments: Vec<HtmlComment>,
+    /// Number of HTML comments already assigned to a section
+    html_comments_assigned: usize,
+}
+
+impl<'a> ParseState<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            lines: LineIndex::new(source),
+            sections: Vec::new(),
+            content: Cow::Borrowed(""),
+            section_type: None,
+            level: None,
+            range: 0..0,
+            order_idx: 0,
+            variables: Vec::new(),
+            table: None,
+            table_cell: None,
+            in_table_head: false,
+            code: None,
+            heading_id: None,
+            classes: Vec::new(),
+            attributes: BTreeMap::new(),
+            slugger: Slugger::default(),
+            list: None,
+            list_stack: Vec::new(),
+            item_stack: Vec::new(),
+            checklist_items: Vec::new(),
+            links: Vec::new(),
+            images: Vec::new(),
+            open_links: Vec::new(),
+            links_assigned: 0,
+            images_assigned: 0,
+            footnote_label: None,
+            footnote_definitions: Vec::new(),
+            nested_footnote: None,
+            nested_footnotes: Vec::new(),
+            footnote_labels: Vec::new(),
+            footnote_refs: Vec::new(),
+            footnote_refs_assigned: 0,
+            code_ranges: Vec::new(),
+            code_blocks: Vec::new(),
+            html_block: String::new(),
+            html_offsets: Vec::new(),
+            inline_html: Vec::new(),
+            inline_html_assigned: 0,
+            html_comments: Vec::new(),
+            html_comments_assigned: 0,
+        }
+    }
+
+    /// Open a new section covering `range`
+    fn begin(&mut self, section_type: SectionType, range: Range<usize>) {
+        self.section_type = Some(section_type);
+        self.range = range;
+    }
+
+    /// Add a flushed section, numbering it and recording its variables
+    fn push_section(&mut self, mut section: ParsedSectionRef<'a>) {
+        section.order_idx = self.order_idx;
+        self.order_idx += 1;
+        self.variables.extend(section.variables.iter().cloned());
+        self.sections.push(section);
+    }
+
+    /// Attach links, images and other inline items found since the last
+    /// flush to the sections just flushed
+    ///
+    /// When a classifier split the section, each item goes to the part that
+    /// contains it (or the first part).
+    fn assign_owners(&mut self, owners: Range<usize>) {
+        let parts = &self.sections[owners.clone()];
+        let owner = |span: &SourceSpan| {
+            let start = span.byte_range.start;
+            let idx = parts.partition_point(|s| s.span.byte_range.start <= start);
+            (!parts.is_empty()).then(|| owners.start + idx.saturating_sub(1))
+        };
+        for link in &mut self.links[self.links_assigned..] {
+            link.section_idx = owner(&link.span);
+        }
+        for image in &mut self.images[self.images_assigned..] {
+            image.section_idx = owner(&image.span);
+        }
+        for (_, reference) in &mut self.footnote_refs[self.footnote_refs_assigned..] {
+            reference.section_idx = owner(&reference.span);
+        }
+        for fragment in &mut self.inline_html[self.inline_html_assigned..] {
+            fragment.section_idx = owner(&fragment.span);
+        }
+        for comment in &mut self.html_comments[self.html_comments_assigned..] {
+            comment.section_idx = owner(&comment.span);
+        }
+        self.links_assigned = self.links.len();
+        self.images_assigned = self.images.len();
+        self.footnote_refs_assigned = self.footnote_refs.len();
+        self.inline_html_assigned = self.inline_html.len();
+        self.html_comments_assigned = self.html_comments.len();
+    }
+
+    /// Record the comments in the HTML block just read
+    fn push_block_comments(&mut self) {
+        // Map an offset in `html_block` back to the source, which may differ
+        // when the block is nested in a blockquote or list
+        let to_source = |offset: usize| {
+            let idx = self
+                .html_offsets
+                .partition_point(|&(start, _)| start <= offset);
+            let (start, source_start) = self.html_offsets[idx.saturating_sub(1)];
+            source_start + (offset - start)
+        };
+        let comments: Vec<HtmlComment> = comments_in(&self.html_block)
+            .map(|(range, text)| HtmlComment {
+                text: text.to_string(),
+                inline: false,
+                section_idx: None,
+                span: self
+                    .lines
+                    .span(to_source(range.start)..to_source(range.end - 1) + 1),
+            })
+            .collect();
+        self.html_comments.extend(comments);
+    }
+
+    /// Append inline text to the current section and any open cell, list
+    /// item or link
+    fn push_text(&mut self, text: &CowStr<'a>) {
+        self.push_content(text);
+        if let Some(cell) = self.table_cell.as_mut() {
+            cell.push_str(text);
+        }
+        if let Some(item) = self.item_stack.last_mut() {
+            item.text.push_str(text);
+        }
+        for (_, link) in self.open_links.iter_mut() {
+            link.text.push_str(text);
+        }
+    }
+
+    /// Append text to the current section, borrowing it if it is the first
+    fn push_content(&mut self, text: &CowStr<'a>) {
+        match text {
+            CowStr::Borrowed(text) if self.content.is_empty() => {
+                self.content = Cow::Borrowed(text);
+            }
+            _ => self.content.to_mut().push_str(text),
+        }
+    }
+
+    /// Range of the current section without trailing whitespace
+    fn trimmed_range(&self) -> Range<usize> {
+        let slice = &self.source[self.range.clone()];
+        self.range.start..self.range.start + slice.trim_end().len()
+    }
+}
+
+/// Create a link or image from its start tag
+fn new_link(
+    link_type: pulldown_cmark::LinkType,
+    dest_url: &str,
+    title: &str,
+    id: &str,
+    state: &ParseState<'_>,
+    range: Range<usize>,
+) -> ParsedLink {
+    let link_type = LinkType::from(link_type);
+    ParsedLink {
+        url: dest_url.to_string(),
+        title: (!title.is_empty()).then(|| title.to_string()),
+        text: String::new(),
+        link_type,
+        reference: (link_type == LinkType::Reference && !id.is_empty()).then(|| id.to_string()),
+        section_idx: None,
+        span: state.lines.span(range),
+    }
+}
+
+/// Trim text without copying it when it is borrowed or already trimmed
+fn trim_cow(text: Cow<'_, str>) -> Cow<'_, str> {
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(text.trim()),
+        Cow::Owned(text) if text.trim().len() == text.len() => Cow::Owned(text),
+        Cow::Owned(text) => Cow::Owned(text.trim().to_string()),
+    }
+}
+
+/// Convert pulldown-cmark HeadingLevel to u8
+fn heading_level_to_u8(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::callout::CalloutKind;
+    use crate::code::CodeBlockKind;
+    use crate::table::Alignment;
+
+    #[test]
+    fn test_parse_simple() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("# Hello\n\nWorld").unwrap();
+
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].section_type, SectionType::Heading);
+        assert_eq!(doc.sections[0].level, Some(1));
+        assert_eq!(doc.sections[1].section_type, SectionType::Paragraph);
+    }
+
+    #[test]
+    fn test_parse_code_block() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("```rust\nfn main() {}\n```").unwrap();
+
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].section_type, SectionType::Code);
+    }
+
+    #[test]
+    fn test_code_block_metadata() {
+        let parser = MarkdownParser::new();
+        let doc = parser
+            .parse("```yaml title=\"config.yml\"\nkey: value\n```\n\n    indented code")
+            .unwrap();
+
+        let fenced = doc.sections[0].code.as_ref().unwrap();
+        assert_eq!(fenced.kind, CodeBlockKind::Fenced);
+        assert_eq!(fenced.language.as_deref(), Some("yaml"));
+        assert_eq!(fenced.info, "yaml title=\"config.yml\"");
+        assert_eq!(fenced.attribute("title"), Some("config.yml"));
+
+        let indented = doc.sections[1].code.as_ref().unwrap();
+        assert_eq!(indented.kind, CodeBlockKind::Indented);
+        assert_eq!(indented.language, None);
+    }
+
+    #[test]
+    fn test_raw_and_plain_text() {
+        let parser = MarkdownParser::new();
+        let doc = parser
+            .parse("## The *Title*\n\nSome **bold**, `code` and [a link](https://example.com).")
+            .unwrap();
+
+        assert_eq!(doc.sections[0].content, "The Title");
+        assert_eq!(doc.sections[0].raw, "## The *Title*");
+        assert_eq!(doc.sections[1].content, "Some bold, code and a link.");
+        assert_eq!(
+            doc.sections[1].raw,
+            "Some **bold**, `code` and [a link](https://example.com)."
+        );
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("- Item 1\n- Item 2\n- Item 3").unwrap();
+
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].section_type, SectionType::List);
+    }
+
+    #[test]
+    fn test_generate_edges() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("# A\n\nB\n\nC").unwrap();
+
+        assert_eq!(doc.edges.len(), 4); // A->B, B->C follows; A contains B, C
+    }
+
+    #[test]
+    fn test_extract_title() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("# My Document\n\nContent here").unwrap();
+
+        assert_eq!(doc.title, Some("My Document".to_string()));
+        assert_eq!(doc.title_source, Some(TitleSource::FirstH1));
+    }
+
+    #[test]
+    fn test_title_sources() {
+        let markdown = "Intro\n\n## Overview\n\nSetext Title\n============\n";
+
+        let doc = MarkdownParser::new().parse(markdown).unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Setext Title"));
+
+        let doc = MarkdownParser::new().parse("## Overview\n\nBody").unwrap();
+        assert_eq!(doc.title, None);
+        assert_eq!(doc.title_source, None);
+
+        let parser = MarkdownParser::builder()
+            .title_sources(vec![TitleSource::FirstHeading, TitleSource::FirstH1])
+            .build();
+        let doc = parser.parse(markdown).unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Overview"));
+        assert_eq!(doc.title_source, Some(TitleSource::FirstHeading));
+
+        // Empty headings are skipped; filename is unused without parse_file
+        let parser = MarkdownParser::builder()
+            .title_sources(vec![TitleSource::Filename, TitleSource::FirstHeading])
+            .build();
+        let doc = parser.parse("#\n\n### Real\n").unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Real"));
+    }
+
+    #[test]
+    fn test_parse_headers_h1_to_h6() {
+        let parser = MarkdownParser::new();
+        let markdown = "# H1\n\n## H2\n\n### H3\n\n#### H4\n\n##### H5\n\n###### H6";
+        let doc = parser.parse(markdown).unwrap();
+
+        assert_eq!(doc.sections.len(), 6);
+        for (i, section) in doc.sections.iter().enumerate() {
+            assert_eq!(section.section_type, SectionType::Heading);
+            assert_eq!(section.level, Some((i + 1) as u8));
+        }
+    }
+
+    #[test]
+    fn test_parse_blockquote() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("> This is a quote").unwrap();
 
         assert_eq!(doc.sections.len(), 1);
         assert_eq!(doc.sections[0].section_type, SectionType::Blockquote);
@@ -471,8 +1751,13 @@ mod tests {
         let parser = MarkdownParser::new();
         let doc = parser.parse("# A\n\nB\n\nC\n\nD").unwrap();
 
-        assert_eq!(doc.edges.len(), 3);
-        for (i, edge) in doc.edges.iter().enumerate() {
+        let follows: Vec<_> = doc
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Follows)
+            .collect();
+        assert_eq!(follows.len(), 3);
+        for (i, edge) in follows.iter().enumerate() {
             assert_eq!(edge.source_idx, i);
             assert_eq!(edge.target_idx, i + 1);
             assert!(matches!(edge.edge_type, EdgeType::Follows));
@@ -507,6 +1792,10 @@ mod tests {
         // Nested lists should be part of the same list section
         assert_eq!(doc.sections.len(), 1);
         assert_eq!(doc.sections[0].section_type, SectionType::List);
+        assert_eq!(
+            doc.sections[0].content,
+            "Item 1\nNested 1\nNested 2\nItem 2"
+        );
     }
 
     #[test]
@@ -518,4 +1807,805 @@ mod tests {
         assert_eq!(doc.sections.len(), 1);
         assert_eq!(doc.sections[0].section_type, SectionType::Blockquote);
     }
+
+    #[test]
+    fn test_tables_disabled_by_default() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("| A | B |\n|---|---|\n| 1 | 2 |").unwrap();
+
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].section_type, SectionType::Paragraph);
+    }
+
+    #[test]
+    fn test_builder_enables_tables() {
+        let parser = MarkdownParser::builder().tables(true).build();
+        let doc = parser.parse("| A | B |\n|---|---|\n| 1 | 2 |").unwrap();
+
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].section_type, SectionType::Table);
+        assert_eq!(doc.sections[0].content, "A | B\n1 | 2");
+    }
+
+    #[test]
+    fn test_builder_options() {
+        let parser = MarkdownParser::builder()
+            .gfm()
+            .footnotes(false)
+            .heading_attributes(true)
+            .generate_ids(false)
+            .build();
+
+        assert!(parser.options().tables);
+        assert!(!parser.options().footnotes);
+        assert!(parser.options().heading_attributes);
+
+        let doc = parser.parse("# Title").unwrap();
+        assert!(doc.sections[0].id.is_empty());
+    }
+
+    #[test]
+    fn test_task_lists_strip_markers() {
+        let parser = MarkdownParser::builder().task_lists(true).build();
+        let doc = parser.parse("- [ ] Task 1\n- [x] Task 2").unwrap();
+
+        assert_eq!(doc.sections[0].content, "Task 1\nTask 2");
+        assert_eq!(doc.checklist_items.len(), 2);
+    }
+
+    #[test]
+    fn test_math_content_kept() {
+        let parser = MarkdownParser::builder().math(true).build();
+        let doc = parser.parse("Euler: $e^{i\\pi} + 1 = 0$").unwrap();
+
+        assert_eq!(doc.sections[0].content, "Euler: e^{i\\pi} + 1 = 0");
+    }
+
+    #[test]
+    fn test_section_spans() {
+        let parser = MarkdownParser::new();
+        let content = "# Title\n\nFirst line\nsecond line\n\n- a\n- b\n";
+        let doc = parser.parse(content).unwrap();
+
+        let heading = &doc.sections[0].span;
+        assert_eq!(heading.byte_range, 0..7);
+        assert_eq!((heading.start_line, heading.start_col), (1, 1));
+        assert_eq!((heading.end_line, heading.end_col), (1, 8));
+
+        let paragraph = &doc.sections[1].span;
+        assert_eq!(
+            &content[paragraph.byte_range.clone()],
+            "First line\nsecond line"
+        );
+        assert_eq!((paragraph.start_line, paragraph.end_line), (3, 4));
+
+        let list = &doc.sections[2].span;
+        assert_eq!(&content[list.byte_range.clone()], "- a\n- b");
+    }
+
+    #[test]
+    fn test_variable_occurrence_spans() {
+        let parser = MarkdownParser::new();
+        let content = "# Hi\n\nHello {{name}}";
+        let doc = parser.parse(content).unwrap();
+
+        let occurrences = &doc.sections[1].variable_occurrences;
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].name, "name");
+        assert_eq!(&content[occurrences[0].span.byte_range.clone()], "{{name}}");
+        assert_eq!(
+            (
+                occurrences[0].span.start_line,
+                occurrences[0].span.start_col
+            ),
+            (3, 7)
+        );
+    }
+
+    #[cfg(feature = "frontmatter")]
+    #[test]
+    fn test_spans_include_frontmatter_offset() {
+        let parser = MarkdownParser::new();
+        let content = "---\ntitle: T\n---\n\n# Heading\n\n- [ ] Task";
+        let doc = parser.parse(content).unwrap();
+
+        let heading = &doc.sections[0].span;
+        assert_eq!(&content[heading.byte_range.clone()], "# Heading");
+        assert_eq!(heading.start_line, 5);
+
+        let item = &doc.checklist_items[0].span;
+        assert_eq!(&content[item.byte_range.clone()], "- [ ] Task");
+        assert_eq!(item.start_line, 7);
+    }
+
+    #[test]
+    fn test_contains_edges() {
+        let parser = MarkdownParser::new();
+        let doc = parser
+            .parse("# A\n\nIntro\n\n## B\n\nText\n\n### C\n\n- item\n\n## D\n\nMore")
+            .unwrap();
+
+        let contains: Vec<_> = doc
+            .edges
+            .iter()
+            .filter(|e| e.edge_type == EdgeType::Contains)
+            .map(|e| (e.source_idx, e.target_idx))
+            .collect();
+        assert_eq!(
+            contains,
+            vec![(0, 1), (0, 2), (2, 3), (2, 4), (4, 5), (0, 6), (6, 7)]
+        );
+    }
+
+    #[test]
+    fn test_heading_parents_without_top_heading() {
+        let parser = MarkdownParser::new();
+        let doc = parser
+            .parse("Preamble\n\n### Deep\n\n## Shallow\n\nText")
+            .unwrap();
+
+        assert_eq!(
+            heading_parents(&doc.sections),
+            vec![None, None, None, Some(2)]
+        );
+    }
+
+    #[test]
+    fn test_table_model() {
+        let parser = MarkdownParser::builder().tables(true).build();
+        let doc = parser
+            .parse("| Name | `Code` | Score |\n|:-----|:------:|------:|\n| **Ann** | a1 | 10 |\n| Bob | b2 | |")
+            .unwrap();
+
+        let table = doc.sections[0].table.as_ref().unwrap();
+        assert_eq!(table.headers, vec!["Name", "Code", "Score"]);
+        assert_eq!(
+            table.alignments,
+            vec![Alignment::Left, Alignment::Center, Alignment::Right]
+        );
+        assert_eq!(table.rows[0], vec!["Ann", "a1", "10"]);
+        assert_eq!(table.rows[1], vec!["Bob", "b2", ""]);
+        assert_eq!(
+            doc.sections[0].content,
+            "Name | Code | Score\nAnn | a1 | 10\nBob | b2 |"
+        );
+    }
+
+    #[test]
+    fn test_non_table_sections_have_no_table() {
+        let parser = MarkdownParser::builder().tables(true).build();
+        let doc = parser.parse("# Title\n\nText").unwrap();
+
+        assert!(doc.sections.iter().all(|s| s.table.is_none()));
+    }
+
+    #[test]
+    fn test_link_extraction() {
+        let parser = MarkdownParser::new();
+        let content = "# Links\n\nAn [inline](https://a.example \"A\") link, <https://b.example>,\n<me@example.com> and [ref][r].\n\n[r]: https://r.example";
+        let doc = parser.parse(content).unwrap();
+
+        assert_eq!(doc.links.len(), 4);
+        let inline = &doc.links[0];
+        assert_eq!(inline.url, "https://a.example");
+        assert_eq!(inline.title.as_deref(), Some("A"));
+        assert_eq!(inline.text, "inline");
+        assert_eq!(inline.link_type, LinkType::Inline);
+        assert_eq!(inline.section_idx, Some(1));
+        assert_eq!(
+            &content[inline.span.byte_range.clone()],
+            "[inline](https://a.example \"A\")"
+        );
+
+        assert_eq!(doc.links[1].link_type, LinkType::Autolink);
+        assert_eq!(doc.links[2].link_type, LinkType::Email);
+        assert_eq!(doc.links[2].url, "me@example.com");
+        assert_eq!(doc.links[3].link_type, LinkType::Reference);
+        assert_eq!(doc.links[3].url, "https://r.example");
+        assert_eq!(doc.links[3].reference.as_deref(), Some("r"));
+
+        assert_eq!(doc.link_definitions.len(), 1);
+        assert_eq!(doc.link_definitions[0].label, "r");
+        assert_eq!(doc.link_definitions[0].span.start_line, 6);
+    }
+
+    #[test]
+    fn test_tags_and_mentions() {
+        let parser = MarkdownParser::new();
+        let doc = parser
+            .parse(
+                "# Plan #q3 {#plan}\n\n@alice owns [#not-tag](#anchor), `@not` and \
+                 <span data-x=\"#no\">#yes</span>\n\n```\n#comment @bot\n```\n\n- [ ] #q3 for @bob\n\n\
+                 - Run\n\n  ```sh\n  #!/bin/sh @bot\n  ```\n\n  ~~~\n  #nested\n  ~~~\n\n\
+                 > ```\n> #quoted @quoted\n> ```\n",
+            )
+            .unwrap();
+
+        assert_eq!(doc.sections[0].tags, vec!["q3"]);
+        assert_eq!(doc.sections[1].tags, vec!["yes"]);
+        assert_eq!(doc.sections[1].mentions, vec!["alice"]);
+        assert!(doc.sections[2].tags.is_empty());
+        assert_eq!(doc.sections[3].mentions, vec!["bob"]);
+        assert!(doc.sections[4..].iter().all(|s| s.tags.is_empty()));
+        assert!(doc.sections[4..].iter().all(|s| s.mentions.is_empty()));
+        assert_eq!(doc.tags.iter().collect::<Vec<_>>(), vec!["q3", "yes"]);
+        assert_eq!(
+            doc.mentions.iter().collect::<Vec<_>>(),
+            vec!["alice", "bob"]
+        );
+    }
+
+    #[test]
+    fn test_image_extraction() {
+        let parser = MarkdownParser::new();
+        let doc = parser
+            .parse("Intro\n\n- [![Badge](https://img.example/b.svg)](https://ci.example)")
+            .unwrap();
+
+        assert_eq!(doc.images.len(), 1);
+        assert_eq!(doc.images[0].url, "https://img.example/b.svg");
+        assert_eq!(doc.images[0].text, "Badge");
+        assert_eq!(doc.images[0].section_idx, Some(1));
+
+        assert_eq!(doc.links.len(), 1);
+        assert_eq!(doc.links[0].url, "https://ci.example");
+        assert_eq!(doc.links[0].text, "Badge");
+        assert_eq!(doc.links[0].section_idx, Some(1));
+    }
+
+    #[test]
+    fn test_footnote_definitions() {
+        let parser = MarkdownParser::builder().footnotes(true).build();
+        let content = "Claim[^note] here.\n\n[^note]: First para.\n\n    - nested item\n\nAfter.";
+        let doc = parser.parse(content).unwrap();
+
+        assert_eq!(doc.sections.len(), 3);
+        assert_eq!(doc.sections[0].content, "Claim here.");
+        assert_eq!(
+            doc.sections[1].section_type,
+            SectionType::FootnoteDefinition
+        );
+        assert_eq!(doc.sections[1].content, "First para.\nnested item");
+        assert_eq!(doc.sections[2].content, "After.");
+
+        let note = &doc.footnotes["note"];
+        assert_eq!(note.definition_idx, Some(1));
+        assert_eq!(note.references.len(), 1);
+        assert_eq!(note.references[0].section_idx, Some(0));
+        assert_eq!(
+            &content[note.references[0].span.byte_range.clone()],
+            "[^note]"
+        );
+    }
+
+    #[test]
+    fn test_footnote_definitions_in_containers() {
+        let parser = MarkdownParser::builder().footnotes(true).build();
+        let doc = parser
+            .parse("> quote a[^1]\n>\n> [^1]: note\n>\n> more quote")
+            .unwrap();
+
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].section_type, SectionType::Blockquote);
+        assert!(doc.sections[0].content.starts_with("quote a"));
+        assert!(doc.sections[0].content.ends_with("more quote"));
+        let note = &doc.footnotes["1"];
+        assert_eq!(note.content.as_deref(), Some("note"));
+        assert_eq!(note.definition_idx, Some(0));
+        assert_eq!(note.references.len(), 1);
+
+        let doc = parser
+            .parse("Intro[^1]\n\n- item\n\n  [^1]: note\n\n  more item\n\nAfter")
+            .unwrap();
+
+        let types: Vec<_> = doc.sections.iter().map(|s| s.section_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                SectionType::Paragraph,
+                SectionType::List,
+                SectionType::Paragraph
+            ]
+        );
+        assert!(doc.sections[1].content.ends_with("more item"));
+        let list = doc.sections[1].list.as_ref().unwrap();
+        assert!(list.items[0].text.ends_with("more item"));
+        let note = &doc.footnotes["1"];
+        assert_eq!(note.content.as_deref(), Some("note"));
+        assert_eq!(note.definition_idx, Some(1));
+        assert_eq!(note.referencing_sections(), vec![0]);
+        assert!(doc.dangling_footnotes().is_empty());
+    }
+
+    #[test]
+    fn test_footnotes_skip_nested_code_blocks() {
+        let parser = MarkdownParser::builder().footnotes(true).build();
+        let doc = parser
+            .parse("- item\n\n  ```\n  x[^1]\n  ```\n\n> ~~~\n> y[^2]\n> ~~~\n")
+            .unwrap();
+
+        assert!(doc.dangling_footnotes().is_empty());
+        assert!(doc.footnotes.is_empty());
+    }
+
+    #[test]
+    fn test_footnote_dangling_and_unused() {
+        let parser = MarkdownParser::builder().footnotes(true).build();
+        let doc = parser
+            .parse("A[^1], B[^Two], C[^missing], `[^code]`.\n\n[^1]: One\n\n[^two]: Two\n\n[^spare]: Spare")
+            .unwrap();
+
+        assert_eq!(doc.footnotes.len(), 4);
+        assert_eq!(doc.footnotes["two"].references.len(), 1);
+
+        let dangling: Vec<_> = doc.dangling_footnotes().iter().map(|f| &f.label).collect();
+        assert_eq!(dangling, vec!["missing"]);
+        assert_eq!(doc.footnotes["missing"].references[0].section_idx, Some(0));
+
+        let unused: Vec<_> = doc.unused_footnotes().iter().map(|f| &f.label).collect();
+        assert_eq!(unused, vec!["spare"]);
+    }
+
+    #[test]
+    fn test_footnotes_disabled() {
+        let parser = MarkdownParser::new();
+        let doc = parser.parse("A[^1].\n\n[^1]: One").unwrap();
+
+        assert!(doc.footnotes.is_empty());
+        assert!(doc
+            .sections
+            .iter()
+            .all(|s| s.section_type != SectionType::FootnoteDefinition));
+    }
+
+    #[test]
+    fn test_html_block_sections() {
+        let parser = MarkdownParser::without_ids();
+        let content = "Intro\n\n<details>\n<summary>More</summary>\n\nInner text\n\n</details>";
+        let doc = parser.parse(content).unwrap();
+
+        let types: Vec<_> = doc.sections.iter().map(|s| s.section_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                SectionType::Paragraph,
+                SectionType::Html,
+                SectionType::Paragraph,
+                SectionType::Html
+            ]
+        );
+        assert_eq!(
+            doc.sections[1].content,
+            "<details>\n<summary>More</summary>"
+        );
+        assert_eq!(doc.sections[3].raw, "</details>");
+    }
+
+    #[test]
+    fn test_inline_html() {
+        let parser = MarkdownParser::without_ids();
+        let doc = parser
+            .parse("# Title\n\nText with <span class=\"x\">inline</span> HTML.")
+            .unwrap();
+
+        assert_eq!(doc.sections[1].content, "Text with inline HTML.");
+        let html: Vec<_> = doc.inline_html.iter().map(|f| f.html.as_str()).collect();
+        assert_eq!(html, vec!["<span class=\"x\">", "</span>"]);
+        assert!(doc.inline_html.iter().all(|f| f.section_idx == Some(1)));
+        assert_eq!(doc.inline_html[1].span.byte_range, 41..48);
+    }
+
+    #[test]
+    fn test_html_comments() {
+        let parser = MarkdownParser::without_ids();
+        let content = "<!-- owner: team-a -->\n\nText <!-- inline note --> here.\n\n> Note\n>\n> <!--\n> status: draft\n> -->";
+        let doc = parser.parse(content).unwrap();
+
+        assert_eq!(doc.html_comments.len(), 3);
+        assert!(doc.inline_html.is_empty());
+
+        let block = &doc.html_comments[0];
+        assert_eq!(block.text, "owner: team-a");
+        assert!(!block.inline);
+        assert_eq!(block.section_idx, Some(0));
+        assert_eq!(doc.sections[0].section_type, SectionType::Html);
+
+        let inline = &doc.html_comments[1];
+        assert_eq!(inline.text, "inline note");
+        assert!(inline.inline);
+        assert_eq!(inline.section_idx, Some(1));
+        assert_eq!(
+            &content[inline.span.byte_range.clone()],
+            "<!-- inline note -->"
+        );
+
+        // Comments nested in a blockquote belong to the blockquote section
+        let nested = &doc.html_comments[2];
+        assert_eq!(nested.text, "status: draft");
+        assert_eq!(nested.section_idx, Some(2));
+        assert_eq!(
+            &content[nested.span.byte_range.clone()],
+            "<!--\n> status: draft\n> -->"
+        );
+        assert_eq!(doc.annotation("status"), Some("draft"));
+        assert_eq!(doc.annotation("owner"), Some("team-a"));
+    }
+
+    #[test]
+    fn test_list_model() {
+        let parser = MarkdownParser::without_ids();
+        let content = "- Item 1\n  - Nested 1\n  - Nested 2\n- Item 2\n\n# Next";
+        let doc = parser.parse(content).unwrap();
+
+        let list = doc.sections[0].list.as_ref().unwrap();
+        assert!(!list.ordered);
+        assert_eq!(list.marker, '-');
+        assert!(list.tight);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.items[0].text, "Item 1");
+        assert_eq!(list.items[1].text, "Item 2");
+        assert_eq!(
+            &content[list.items[0].span.byte_range.clone()],
+            "- Item 1\n  - Nested 1\n  - Nested 2"
+        );
+
+        let nested = &list.items[0].children[0];
+        let texts: Vec<_> = nested.items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(texts, vec!["Nested 1", "Nested 2"]);
+        assert!(doc.sections[1].list.is_none());
+    }
+
+    #[test]
+    fn test_ordered_loose_list() {
+        let parser = MarkdownParser::without_ids();
+        let doc = parser
+            .parse("7) First\n\n8) Second\n   continued\n\n   More")
+            .unwrap();
+
+        let list = doc.sections[0].list.as_ref().unwrap();
+        assert!(list.ordered);
+        assert_eq!(list.start, Some(7));
+        assert_eq!(list.marker, ')');
+        assert!(!list.tight);
+        assert_eq!(list.items[1].text, "Second\ncontinued\nMore");
+        assert_eq!(list.number(1), Some(8));
+    }
+
+    #[test]
+    fn test_list_task_items() {
+        let parser = MarkdownParser::builder().task_lists(true).build();
+        let doc = parser.parse("* [x] Done\n* [ ] Todo\n* Plain").unwrap();
+
+        let list = doc.sections[0].list.as_ref().unwrap();
+        let checked: Vec<_> = list.items.iter().map(|i| i.checked).collect();
+        assert_eq!(checked, vec![Some(true), Some(false), None]);
+        assert_eq!(list.marker, '*');
+    }
+
+    #[test]
+    fn test_checklist_sections() {
+        let parser = MarkdownParser::without_ids();
+        let doc = parser
+            .parse("- [ ] One\n- [x] Two\n\n# Mixed\n\n- [ ] Task\n- Note\n\n- plain")
+            .unwrap();
+
+        assert_eq!(doc.sections[0].section_type, SectionType::Checklist);
+        assert_eq!(doc.sections[2].section_type, SectionType::List);
+
+        let owners: Vec<_> = doc.checklist_items.iter().map(|i| i.section_idx).collect();
+        assert_eq!(owners, vec![Some(0), Some(0), Some(2)]);
+    }
+
+    #[test]
+    fn test_checklist_sections_with_task_lists() {
+        let parser = MarkdownParser::builder().task_lists(true).build();
+        let doc = parser.parse("Intro\n\n- [x] Done\n  - [ ] Sub").unwrap();
+
+        assert_eq!(doc.sections[1].section_type, SectionType::Checklist);
+        assert_eq!(doc.checklist_items_in(1).len(), 2);
+        assert_eq!(doc.sections[1].content, "Done\nSub");
+    }
+
+    #[test]
+    fn test_checklist_marker_forms() {
+        let content = "* [ ] Star\n\n+ [x] Plus\n\n1. [ ] One\n2) [X] Two\n\n> - [ ] Quoted\n\n\
+                       ```\n- [ ] Not a task\n```";
+        for task_lists in [false, true] {
+            let parser = MarkdownParser::builder().task_lists(task_lists).build();
+            let doc = parser.parse(content).unwrap();
+
+            let items: Vec<_> = doc
+                .checklist_items
+                .iter()
+                .map(|i| (i.text.as_str(), i.checked, i.section_idx))
+                .collect();
+            assert_eq!(
+                items,
+                vec![
+                    ("Star", false, Some(0)),
+                    ("Plus", true, Some(1)),
+                    ("One", false, Some(2)),
+                    ("Two", true, Some(3)),
+                    ("Quoted", false, Some(4)),
+                ]
+            );
+            for section in &doc.sections[..5] {
+                assert_eq!(section.section_type, SectionType::Checklist);
+            }
+            assert_eq!(doc.sections[5].section_type, SectionType::Code);
+            assert_eq!(
+                &content[doc.checklist_items[4].span.byte_range.clone()],
+                "- [ ] Quoted"
+            );
+        }
+    }
+
+    #[test]
+    fn test_choice_sections() {
+        let parser = MarkdownParser::without_ids();
+        let content =
+            "- ( ) Small\n- (x) Medium\n\n<!-- choice -->\n\n- [x] Email\n- [ ] SMS\n\n## Tasks\n\n- [ ] Task";
+        let doc = parser.parse(content).unwrap();
+
+        let types: Vec<_> = doc.sections.iter().map(|s| s.section_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                SectionType::Choice,
+                SectionType::Html,
+                SectionType::Choice,
+                SectionType::Heading,
+                SectionType::Checklist
+            ]
+        );
+
+        let radio = doc.sections[0].choice.as_ref().unwrap();
+        assert!(!radio.multi);
+        assert_eq!(radio.selected_texts(), vec!["Medium"]);
+        assert_eq!(
+            &content[radio.options[1].span.byte_range.clone()],
+            "- (x) Medium"
+        );
+
+        let multi = doc.sections[2].choice.as_ref().unwrap();
+        assert!(multi.multi);
+        assert_eq!(multi.selected, vec![0]);
+        assert!(doc.sections[4].choice.is_none());
+    }
+
+    #[test]
+    fn test_choice_directive_with_task_lists() {
+        let parser = MarkdownParser::builder().task_lists(true).build();
+        let doc = parser
+            .parse("<!-- choice: single -->\n- [ ] Yes\n- [x] No")
+            .unwrap();
+
+        let choice = doc.sections[1].choice.as_ref().unwrap();
+        assert!(!choice.multi);
+        assert_eq!(choice.options[0].text, "Yes");
+        assert_eq!(choice.selected, vec![1]);
+    }
+
+    #[test]
+    fn test_callouts() {
+        let parser = MarkdownParser::without_ids();
+        let doc = parser
+            .parse("> [!NOTE]\n> First line\n>\n> Second para\n\n> Plain quote")
+            .unwrap();
+
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(doc.sections[0].content, "[!NOTE]\nFirst line\nSecond para");
+
+        let callout = doc.sections[0].callout.as_ref().unwrap();
+        assert_eq!(callout.kind, CalloutKind::Note);
+        assert_eq!(callout.title, None);
+        assert!(!callout.foldable);
+        assert_eq!(callout.content, "First line\nSecond para");
+
+        assert_eq!(doc.sections[1].section_type, SectionType::Blockquote);
+        assert!(doc.sections[1].callout.is_none());
+    }
+
+    #[test]
+    fn test_callout_obsidian_title() {
+        let parser = MarkdownParser::without_ids();
+        let doc = parser.parse("> [!faq]- Why *this*?\n> Because.").unwrap();
+
+        let callout = doc.sections[0].callout.as_ref().unwrap();
+        assert_eq!(callout.kind, CalloutKind::Other("faq".to_string()));
+        assert_eq!(callout.title.as_deref(), Some("Why this?"));
+        assert!(callout.collapsed);
+    }
+
+    #[test]
+    fn test_heading_anchors() {
+        let parser = MarkdownParser::without_ids();
+        let doc = parser
+            .parse("# Intro\n\nText\n\n## Intro\n\n## *Intro*")
+            .unwrap();
+
+        let anchors: Vec<_> = doc.sections.iter().map(|s| s.anchor.as_deref()).collect();
+        assert_eq!(
+            anchors,
+            vec![Some("intro"), None, Some("intro-1"), Some("intro-2")]
+        );
+    }
+
+    #[test]
+    fn test_custom_heading_ids() {
+        let parser = MarkdownParser::builder().heading_attributes(true).build();
+        let doc = parser
+            .parse("## Setup {#install .optional data-step=1}\n\n## Install")
+            .unwrap();
+
+        let section = &doc.sections[0];
+        assert_eq!(section.content, "Setup");
+        assert_eq!(section.anchor.as_deref(), Some("install"));
+        assert_eq!(section.classes, vec!["optional"]);
+        assert_eq!(section.attributes["data-step"].as_deref(), Some("1"));
+
+        // Generated slugs do not collide with explicit IDs
+        assert_eq!(doc.sections[1].anchor.as_deref(), Some("install-1"));
+        assert_eq!(doc.section_by_anchor("install").unwrap().content, "Setup");
+    }
+
+    #[test]
+    fn test_parse_ref_matches_parse() {
+        let input = "# Story {{name}}\n\nOne line\n\nTwo\nlines with *emphasis*\n\n\
+                     ## Tasks\n\n- [x] Write (AC: 1, 2)\n- [ ] Review\n\n> [!NOTE]\n> Careful\n\n\
+                     ```rust\nfn main() {}\n```";
+        let parser = MarkdownParser::builder()
+            .gfm()
+            .id_strategy(IdStrategy::ContentHash)
+            .build();
+
+        let borrowed = parser.parse_ref(input).unwrap();
+        assert_eq!(borrowed.checklist_items[0].ac_refs, vec!["1", "2"]);
+        assert_eq!(
+            format!("{:?}", borrowed.into_owned()),
+            format!("{:?}", parser.parse(input).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_ref_borrows_from_input() {
+        let input = "# Hello {{name}}\n\nTwo\nlines\n\n- [ ] Task";
+        let doc = MarkdownParser::new().parse_ref(input).unwrap();
+        let within = |text: &str| input.as_bytes().as_ptr_range().contains(&text.as_ptr());
+
+        assert!(matches!(doc.sections[0].content, Cow::Borrowed(_)));
+        assert!(within(&doc.sections[0].content));
+        assert!(matches!(doc.variables[0], Cow::Borrowed("name")));
+        assert!(within(doc.sections[1].raw));
+        assert!(within(doc.checklist_items[0].text));
+
+        // Text spanning a line break has to be copied
+        assert_eq!(doc.sections[1].content, "Two\nlines");
+        assert!(matches!(doc.sections[1].content, Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_classifier_split_and_drop() {
+        #[derive(Debug)]
+        struct SplitLines;
+
+        impl SectionClassifier for SplitLines {
+            fn classify<'a>(
+                &self,
+                section: ParsedSectionRef<'a>,
+                ctx: &ClassifyContext<'a, '_>,
+            ) -> Vec<ParsedSectionRef<'a>> {
+                if section.content.starts_with("DROP") {
+                    return Vec::new();
+                }
+                if section.section_type != SectionType::Paragraph {
+                    return vec![section];
+                }
+                let mut start = section.span.byte_range.start;
+                section
+                    .raw
+                    .split_inclusive('\n')
+                    .filter_map(|line| {
+                        let part = ctx.section(SectionType::Paragraph, start..start + line.len());
+                        start += line.len();
+                        part
+                    })
+                    .collect()
+            }
+        }
+
+        let parser = MarkdownParser::builder()
+            .generate_ids(false)
+            .classifier(SplitLines)
+            .build();
+        let doc = parser
+            .parse("# Title\n\nSee [a](a.md)\nand [b](b.md)\n\nDROP [c](c.md)\n\nEnd")
+            .unwrap();
+
+        let contents: Vec<_> = doc.sections.iter().map(|s| s.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["Title", "See [a](a.md)", "and [b](b.md)", "End"]
+        );
+        let order: Vec<_> = doc.sections.iter().map(|s| s.order_idx).collect();
+        assert_eq!(order, vec![0, 1, 2, 3]);
+
+        let owners: Vec<_> = doc.links.iter().map(|l| l.section_idx).collect();
+        assert_eq!(owners, vec![Some(1), Some(2), None]);
+    }
+
+    #[test]
+    fn test_parse_skips_bom() {
+        let input = "\u{feff}# Title\n\nBody";
+        let doc = MarkdownParser::new().parse(input).unwrap();
+
+        assert_eq!(doc.title.as_deref(), Some("Title"));
+        assert_eq!(doc.sections[0].section_type, SectionType::Heading);
+        assert_eq!(doc.sections[0].span.byte_range, 3..10);
+        assert_eq!(doc.encoding, None);
+    }
+
+    #[test]
+    fn test_parse_bytes_keeps_offsets() {
+        let bytes = b"\xEF\xBB\xBF# Title\r\n\r\nOne\r\nTwo #tag\r\n\r\n- [x] Done\r\n";
+        let doc = MarkdownParser::new().parse_bytes(bytes).unwrap();
+
+        let text = std::str::from_utf8(bytes).unwrap();
+        assert_eq!(doc.sections.len(), 3);
+        for section in &doc.sections {
+            assert_eq!(&text[section.span.byte_range.clone()], section.raw);
+        }
+        assert_eq!(doc.sections[0].span.byte_range, 3..10);
+        assert_eq!(doc.sections[1].span.start_line, 3);
+        assert_eq!(doc.sections[1].span.end_line, 4);
+        assert_eq!(doc.sections[1].tags, vec!["tag"]);
+        assert_eq!(doc.checklist_items[0].text, "Done");
+        assert_eq!(doc.checklist_items[0].span.start_line, 6);
+    }
+
+    #[test]
+    fn test_parse_bytes_normalizes_line_endings() {
+        let parser = MarkdownParser::builder()
+            .normalize_line_endings(true)
+            .build();
+        let doc = parser
+            .parse_bytes(b"# Title\r\n\r\nOne\rTwo\r\n\r\n- [x] Done\r\n")
+            .unwrap();
+
+        assert_eq!(doc.sections[1].raw, "One\nTwo");
+        assert_eq!(doc.sections[2].raw, "- [x] Done");
+        assert_eq!(doc.sections[2].span.byte_range, 18..28);
+        assert_eq!(doc.checklist_items[0].text, "Done");
+
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("# Hi".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let doc = parser.parse_bytes(&utf16).unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Hi"));
+        assert_eq!(doc.encoding.as_deref(), Some("UTF-16LE"));
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn test_parse_bytes_fallback_encoding() {
+        let bytes = b"# Gr\xFC\xDFe";
+        let doc = MarkdownParser::new().parse_bytes(bytes).unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Grüße"));
+        assert_eq!(doc.encoding.as_deref(), Some("windows-1252"));
+
+        let parser = MarkdownParser::builder()
+            .fallback_encoding("koi8-r")
+            .build();
+        assert_eq!(parser.fallback_encoding(), Some("koi8-r"));
+        let doc = parser.parse_bytes(b"# \xF0\xD2\xC9\xD7\xC5\xD4").unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Привет"));
+        assert_eq!(doc.encoding.as_deref(), Some("KOI8-R"));
+
+        let parser = MarkdownParser::builder().fallback_encoding("bogus").build();
+        assert!(matches!(
+            parser.parse_bytes(b"# Hi"),
+            Err(ParseError::InvalidEncoding(_))
+        ));
+    }
 }
@@ -2,14 +2,34 @@
 //!
 //! This module provides Python wrappers for the md-parser types and functions.
 
+use std::io::BufRead;
+
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
+use crate::callout::Callout;
 use crate::checklist::{self, ChecklistItem, ChecklistSummary};
+use crate::choice::ChoiceGroup;
+use crate::classifier::PrefixClassifier;
+use crate::code::CodeBlockInfo;
 use crate::document::{ParsedDocument, ParsedEdge};
+use crate::error::ParseError;
+use crate::fields::FieldValue;
+use crate::footnote::Footnote;
+use crate::html::{HtmlComment, HtmlFragment};
+use crate::id::IdStrategy;
+use crate::incremental::{ChangeSet, IncrementalParser};
+use crate::link::ParsedLink;
+use crate::list::{ListItem, ParsedList};
+use crate::options::ParserOptions;
 use crate::parser::MarkdownParser;
 use crate::section::ParsedSection;
-use crate::variables;
+use crate::span::SourceSpan;
+use crate::stream::{SectionStream, StreamedSection};
+use crate::table::ParsedTable;
+use crate::title::TitleSource;
+use crate::variables::{self, VariableOccurrence};
+use crate::wiki::WikiLink;
 
 /// Python wrapper for MarkdownParser
 #[pyclass(name = "MarkdownParser")]
@@ -20,11 +40,133 @@ pub struct PyMarkdownParser {
 #[pymethods]
 impl PyMarkdownParser {
     /// Create a new MarkdownParser
+    ///
+    /// Args:
+    ///     gfm: Enable GitHub Flavored Markdown extensions (tables,
+    ///         strikethrough, footnotes, task lists)
+    ///     id_strategy: How section IDs are generated: "random" (default),
+    ///         "content_hash", "heading_slug" or "none"
+    ///     title_sources: Where to look for the title, tried in order:
+    ///         "frontmatter", "frontmatter:<key>", "h1" (default),
+    ///         "heading" or "filename"
+    ///     section_prefixes: (prefix, type) pairs; paragraphs whose Markdown
+    ///         starts with a prefix get section_type "custom" and that
+    ///         custom_type
+    ///     coerce_fields: Convert inline field values to numbers, booleans,
+    ///         dates and lists instead of keeping them as strings
+    ///     tables, strikethrough, footnotes, task_lists, heading_attributes,
+    ///         smart_punctuation, math: Keyword-only switches for each
+    ///         Markdown extension; when given, they override the `gfm` preset
+    ///     normalize_line_endings: Keyword-only; make `parse_bytes` convert
+    ///         line endings to "\n" (spans then refer to the normalized text)
+    ///     fallback_encoding: Keyword-only encoding label, such as "cp1251",
+    ///         for `parse_bytes` input that is not UTF-8 (requires the
+    ///         `encoding` feature; Windows-1252 by default)
+    ///
+    /// Raises:
+    ///     ValueError: If the ID strategy or a title source is unknown, or
+    ///         `fallback_encoding` is given without the `encoding` feature
     #[new]
-    pub fn new() -> Self {
-        Self {
-            inner: MarkdownParser::new(),
+    #[pyo3(signature = (
+        gfm = false,
+        id_strategy = "random",
+        title_sources = None,
+        section_prefixes = None,
+        coerce_fields = false,
+        *,
+        tables = None,
+        strikethrough = None,
+        footnotes = None,
+        task_lists = None,
+        heading_attributes = None,
+        smart_punctuation = None,
+        math = None,
+        normalize_line_endings = false,
+        fallback_encoding = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        gfm: bool,
+        id_strategy: &str,
+        title_sources: Option<Vec<String>>,
+        section_prefixes: Option<Vec<(String, String)>>,
+        coerce_fields: bool,
+        tables: Option<bool>,
+        strikethrough: Option<bool>,
+        footnotes: Option<bool>,
+        task_lists: Option<bool>,
+        heading_attributes: Option<bool>,
+        smart_punctuation: Option<bool>,
+        math: Option<bool>,
+        normalize_line_endings: bool,
+        fallback_encoding: Option<String>,
+    ) -> PyResult<Self> {
+        let mut options = if gfm {
+            ParserOptions::gfm()
+        } else {
+            ParserOptions::default()
+        };
+        for (option, enabled) in [
+            (&mut options.tables, tables),
+            (&mut options.strikethrough, strikethrough),
+            (&mut options.footnotes, footnotes),
+            (&mut options.task_lists, task_lists),
+            (&mut options.heading_attributes, heading_attributes),
+            (&mut options.smart_punctuation, smart_punctuation),
+            (&mut options.math, math),
+        ] {
+            if let Some(enabled) = enabled {
+                *option = enabled;
+            }
+        }
+        let id_strategy = IdStrategy::from_name(id_strategy).ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Unknown ID strategy: {:?}",
+                id_strategy
+            ))
+        })?;
+        let title_sources = match title_sources {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    TitleSource::from_name(name).ok_or_else(|| {
+                        pyo3::exceptions::PyValueError::new_err(format!(
+                            "Unknown title source: {:?}",
+                            name
+                        ))
+                    })
+                })
+                .collect::<PyResult<Vec<_>>>()?,
+            None => TitleSource::defaults(),
+        };
+        let mut builder = MarkdownParser::builder()
+            .options(options)
+            .id_strategy(id_strategy)
+            .title_sources(title_sources)
+            .coerce_fields(coerce_fields)
+            .normalize_line_endings(normalize_line_endings);
+        if let Some(label) = fallback_encoding {
+            #[cfg(feature = "encoding")]
+            {
+                builder = builder.fallback_encoding(label);
+            }
+            #[cfg(not(feature = "encoding"))]
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "fallback_encoding {:?} requires the `encoding` feature",
+                label
+            )));
+        }
+        if let Some(prefixes) = section_prefixes {
+            let classifier = prefixes
+                .into_iter()
+                .fold(PrefixClassifier::new(), |classifier, (prefix, name)| {
+                    classifier.rule(prefix, name)
+                });
+            builder = builder.classifier(classifier);
         }
+        Ok(Self {
+            inner: builder.build(),
+        })
     }
 
     /// Parse Markdown content into a structured document
@@ -44,6 +186,27 @@ impl PyMarkdownParser {
             .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
     }
 
+    /// Parse raw Markdown bytes, honoring a BOM
+    ///
+    /// Spans are byte offsets into `data` unless the parser normalizes line
+    /// endings.
+    ///
+    /// Args:
+    ///     data: The Markdown bytes (UTF-8, UTF-16 with a BOM, or the fallback
+    ///         encoding when built with the `encoding` feature)
+    ///
+    /// Returns:
+    ///     ParsedDocument with `encoding` set to the encoding used
+    ///
+    /// Raises:
+    ///     ValueError: If the bytes cannot be decoded or parsing fails
+    pub fn parse_bytes(&self, data: &[u8]) -> PyResult<PyParsedDocument> {
+        self.inner
+            .parse_bytes(data)
+            .map(PyParsedDocument::from)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
     /// Parse Markdown from a file path
     ///
     /// Args:
@@ -66,15 +229,255 @@ impl PyMarkdownParser {
                 _ => pyo3::exceptions::PyValueError::new_err(e.to_string()),
             })
     }
+
+    /// Parse many Markdown files, in parallel when built with `parallel`
+    ///
+    /// Args:
+    ///     paths: Paths of the Markdown files
+    ///
+    /// Returns:
+    ///     Tuple of (documents by path, error messages by path)
+    pub fn parse_many(&self, py: Python<'_>, paths: Vec<String>) -> PyBatchResult {
+        let results = py.allow_threads(|| self.inner.parse_many(&paths));
+        split_results(results)
+    }
+
+    /// Parse every file under a directory whose path matches a glob pattern
+    ///
+    /// Args:
+    ///     root: Directory to search
+    ///     pattern: Glob pattern relative to root (default "**/*.md")
+    ///
+    /// Returns:
+    ///     Tuple of (documents by path, error messages by path)
+    ///
+    /// Raises:
+    ///     ValueError: If the pattern is malformed
+    #[pyo3(signature = (root, pattern = "**/*.md"))]
+    pub fn parse_dir(&self, py: Python<'_>, root: &str, pattern: &str) -> PyResult<PyBatchResult> {
+        py.allow_threads(|| self.inner.parse_dir(std::path::Path::new(root), pattern))
+            .map(split_results)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Iterate over the sections of Markdown content one chunk at a time
+    ///
+    /// Args:
+    ///     content: The Markdown content to parse
+    ///
+    /// Returns:
+    ///     SectionStream yielding StreamedSection objects
+    pub fn iter_sections(&self, content: String) -> PySectionStream {
+        let reader: Box<dyn BufRead + Send + Sync> = Box::new(std::io::Cursor::new(content));
+        PySectionStream {
+            inner: self.inner.parse_reader(reader),
+        }
+    }
+
+    /// Iterate over the sections of a Markdown file without loading it whole
+    ///
+    /// Args:
+    ///     path: Path to the Markdown file
+    ///
+    /// Returns:
+    ///     SectionStream yielding StreamedSection objects
+    ///
+    /// Raises:
+    ///     FileNotFoundError: If the file doesn't exist
+    pub fn iter_file(&self, path: &str) -> PyResult<PySectionStream> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| pyo3::exceptions::PyFileNotFoundError::new_err(e.to_string()))?;
+        let reader: Box<dyn BufRead + Send + Sync> = Box::new(std::io::BufReader::new(file));
+        Ok(PySectionStream {
+            inner: self.inner.parse_reader(reader),
+        })
+    }
+}
+
+/// Python wrapper for IncrementalParser
+#[pyclass(name = "IncrementalParser")]
+pub struct PyIncrementalParser {
+    inner: IncrementalParser,
+}
+
+#[pymethods]
+impl PyIncrementalParser {
+    /// Create an incremental parser for `content`
+    ///
+    /// Args:
+    ///     content: The initial Markdown content
+    ///     parser: Parser whose settings to use (default settings if omitted)
+    ///
+    /// Raises:
+    ///     ValueError: If the markdown structure is invalid
+    #[new]
+    #[pyo3(signature = (content, parser = None))]
+    pub fn new(content: &str, parser: Option<PyRef<'_, PyMarkdownParser>>) -> PyResult<Self> {
+        let parser = parser.map(|p| p.inner.clone()).unwrap_or_default();
+        IncrementalParser::new(parser, content)
+            .map(|inner| Self { inner })
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Replace the bytes from `start` to `end` with `text` and re-parse
+    ///
+    /// Returns:
+    ///     ChangeSet with the added, removed and modified section indices
+    ///
+    /// Raises:
+    ///     ValueError: If the range is invalid or re-parsing fails
+    pub fn edit(&mut self, start: usize, end: usize, text: &str) -> PyResult<PyChangeSet> {
+        self.inner
+            .edit(start..end, text)
+            .map(PyChangeSet::from)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Current source text
+    #[getter]
+    pub fn source(&self) -> String {
+        self.inner.source().to_string()
+    }
+
+    /// Current parsed document
+    #[getter]
+    pub fn document(&self) -> PyParsedDocument {
+        PyParsedDocument::from(self.inner.document().clone())
+    }
+}
+
+/// Documents and error messages by path, as returned by `parse_many`
+type PyBatchResult = (
+    std::collections::BTreeMap<String, PyParsedDocument>,
+    std::collections::BTreeMap<String, String>,
+);
+
+/// Split per-file results into documents and error messages
+fn split_results(
+    results: std::collections::BTreeMap<std::path::PathBuf, Result<ParsedDocument, ParseError>>,
+) -> PyBatchResult {
+    let mut documents = std::collections::BTreeMap::new();
+    let mut errors = std::collections::BTreeMap::new();
+    for (path, result) in results {
+        let path = path.to_string_lossy().into_owned();
+        match result {
+            Ok(doc) => {
+                documents.insert(path, PyParsedDocument::from(doc));
+            }
+            Err(e) => {
+                errors.insert(path, e.to_string());
+            }
+        }
+    }
+    (documents, errors)
+}
+
+/// Python wrapper for ChangeSet
+#[pyclass(name = "ChangeSet")]
+#[derive(Clone)]
+pub struct PyChangeSet {
+    /// Indices of new sections in the updated document
+    #[pyo3(get)]
+    pub added: Vec<usize>,
+    /// Indices of deleted sections in the previous document
+    #[pyo3(get)]
+    pub removed: Vec<usize>,
+    /// Indices of changed sections in the updated document
+    #[pyo3(get)]
+    pub modified: Vec<usize>,
+}
+
+#[pymethods]
+impl PyChangeSet {
+    fn __repr__(&self) -> String {
+        format!(
+            "ChangeSet(added={:?}, removed={:?}, modified={:?})",
+            self.added, self.removed, self.modified
+        )
+    }
+}
+
+impl From<ChangeSet> for PyChangeSet {
+    fn from(changes: ChangeSet) -> Self {
+        Self {
+            added: changes.added,
+            removed: changes.removed,
+            modified: changes.modified,
+        }
+    }
+}
+
+/// Python iterator over streamed sections
+#[pyclass(name = "SectionStream")]
+pub struct PySectionStream {
+    inner: SectionStream<Box<dyn BufRead + Send + Sync>>,
+}
+
+#[pymethods]
+impl PySectionStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Get the next section
+    ///
+    /// Raises:
+    ///     ValueError: If the input cannot be read or parsed
+    fn __next__(&mut self) -> PyResult<Option<PyStreamedSection>> {
+        self.inner
+            .next()
+            .transpose()
+            .map(|section| section.map(PyStreamedSection::from))
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+}
+
+/// Python wrapper for StreamedSection
+#[pyclass(name = "StreamedSection")]
+#[derive(Clone)]
+pub struct PyStreamedSection {
+    /// The section
+    #[pyo3(get)]
+    pub section: PyParsedSection,
+    /// Checklist items inside the section
+    #[pyo3(get)]
+    pub checklist_items: Vec<PyChecklistItem>,
+}
+
+#[pymethods]
+impl PyStreamedSection {
+    fn __repr__(&self) -> String {
+        format!(
+            "StreamedSection(type={}, checklist_items={})",
+            self.section.section_type,
+            self.checklist_items.len()
+        )
+    }
+}
+
+impl From<StreamedSection> for PyStreamedSection {
+    fn from(streamed: StreamedSection) -> Self {
+        Self {
+            section: PyParsedSection::from(streamed.section),
+            checklist_items: streamed
+                .checklist_items
+                .into_iter()
+                .map(PyChecklistItem::from)
+                .collect(),
+        }
+    }
 }
 
 /// Python wrapper for ParsedDocument
 #[pyclass(name = "ParsedDocument")]
 #[derive(Clone)]
 pub struct PyParsedDocument {
-    /// Document title (from first H1)
+    /// Document title
     #[pyo3(get)]
     pub title: Option<String>,
+    /// Where the title came from ("h1", "heading", "filename", "frontmatter:<key>")
+    #[pyo3(get)]
+    pub title_source: Option<String>,
     /// All sections in the document
     #[pyo3(get)]
     pub sections: Vec<PyParsedSection>,
@@ -87,6 +490,35 @@ pub struct PyParsedDocument {
     /// Checklist items
     #[pyo3(get)]
     pub checklist_items: Vec<PyChecklistItem>,
+    /// Links (reference links resolved)
+    #[pyo3(get)]
+    pub links: Vec<PyParsedLink>,
+    /// Images
+    #[pyo3(get)]
+    pub images: Vec<PyParsedLink>,
+    /// Wiki-links and embeds
+    #[pyo3(get)]
+    pub wiki_links: Vec<PyWikiLink>,
+    /// Inline tags merged with the frontmatter `tags` list
+    #[pyo3(get)]
+    pub tags: std::collections::BTreeSet<String>,
+    /// Mentions from every section
+    #[pyo3(get)]
+    pub mentions: std::collections::BTreeSet<String>,
+    /// Footnotes by label
+    #[pyo3(get)]
+    pub footnotes: std::collections::BTreeMap<String, PyFootnote>,
+    /// Inline HTML fragments (comments excluded)
+    #[pyo3(get)]
+    pub inline_html: Vec<PyHtmlFragment>,
+    /// HTML comments
+    #[pyo3(get)]
+    pub html_comments: Vec<PyHtmlComment>,
+    /// Encoding the input was decoded from (parse_bytes and parse_file only)
+    #[pyo3(get)]
+    pub encoding: Option<String>,
+    /// Inline fields from every section
+    fields_inner: std::collections::BTreeMap<String, FieldValue>,
     /// YAML frontmatter (when frontmatter feature is enabled)
     #[cfg(feature = "frontmatter")]
     frontmatter_inner: Option<std::collections::HashMap<String, serde_yaml::Value>>,
@@ -99,16 +531,21 @@ impl PyParsedDocument {
         let items: Vec<ChecklistItem> = self
             .checklist_items
             .iter()
-            .map(|i| ChecklistItem {
-                text: i.text.clone(),
-                checked: i.checked,
-                indent: i.indent,
-                ac_refs: i.ac_refs.clone(),
+            .map(|i| {
+                ChecklistItem::new(i.text.clone(), i.checked, i.indent)
+                    .with_ac_refs(i.ac_refs.clone())
             })
             .collect();
         PyChecklistSummary::from(ChecklistSummary::from_items(&items))
     }
 
+    /// Get inline fields from every section as a dict; the first occurrence
+    /// of a key wins
+    #[getter]
+    pub fn fields(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
+        fields_to_py(py, &self.fields_inner)
+    }
+
     /// Get frontmatter as a Python dict (requires frontmatter feature)
     #[cfg(feature = "frontmatter")]
     #[getter]
@@ -125,6 +562,42 @@ impl PyParsedDocument {
         }
     }
 
+    /// Get the value of the first `<!-- key: value -->` comment annotation
+    pub fn annotation(&self, key: &str) -> Option<String> {
+        self.html_comments
+            .iter()
+            .filter_map(|c| c.annotation.as_ref())
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Get the checklist items belonging to a section
+    pub fn checklist_items_in(&self, section_idx: usize) -> Vec<PyChecklistItem> {
+        self.checklist_items
+            .iter()
+            .filter(|item| item.section_idx == Some(section_idx))
+            .cloned()
+            .collect()
+    }
+
+    /// Get footnotes that are referenced but never defined
+    pub fn dangling_footnotes(&self) -> Vec<PyFootnote> {
+        self.footnotes
+            .values()
+            .filter(|f| f.definition_idx.is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Get footnotes that are defined but never referenced
+    pub fn unused_footnotes(&self) -> Vec<PyFootnote> {
+        self.footnotes
+            .values()
+            .filter(|f| f.referencing_sections.is_empty())
+            .cloned()
+            .collect()
+    }
+
     /// Get section by index
     pub fn get_section(&self, idx: usize) -> Option<PyParsedSection> {
         self.sections.get(idx).cloned()
@@ -135,6 +608,62 @@ impl PyParsedDocument {
         self.sections.iter().find(|s| s.id == id).cloned()
     }
 
+    /// Get the heading section with the given anchor (leading `#` ignored)
+    pub fn section_by_anchor(&self, anchor: &str) -> Option<PyParsedSection> {
+        let anchor = anchor.strip_prefix('#').unwrap_or(anchor);
+        self.sections
+            .iter()
+            .find(|s| s.anchor.as_deref() == Some(anchor))
+            .cloned()
+    }
+
+    /// Get the index of the heading that contains a section
+    pub fn parent_of(&self, idx: usize) -> Option<usize> {
+        self.edges
+            .iter()
+            .find(|e| e.edge_type == "contains" && e.target_idx == idx)
+            .map(|e| e.source_idx)
+    }
+
+    /// Get the indices of the sections directly contained by a heading
+    pub fn children_of(&self, idx: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|e| e.edge_type == "contains" && e.source_idx == idx)
+            .map(|e| e.target_idx)
+            .collect()
+    }
+
+    /// Get the chain of heading texts leading to a section
+    pub fn heading_path(&self, idx: usize) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut current = match self.sections.get(idx) {
+            Some(section) if section.section_type == "heading" => Some(idx),
+            Some(_) => self.parent_of(idx),
+            None => None,
+        };
+        while let Some(heading_idx) = current {
+            path.push(self.sections[heading_idx].content.clone());
+            current = self.parent_of(heading_idx);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Get all code sections in a language (case-insensitive)
+    pub fn code_blocks(&self, language: &str) -> Vec<PyParsedSection> {
+        self.sections
+            .iter()
+            .filter(|s| {
+                s.code
+                    .as_ref()
+                    .and_then(|c| c.language.as_deref())
+                    .is_some_and(|l| l.eq_ignore_ascii_case(language))
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Get all sections of a specific type
     pub fn sections_by_type(&self, section_type: &str) -> Vec<PyParsedSection> {
         self.sections
@@ -192,6 +721,7 @@ impl From<ParsedDocument> for PyParsedDocument {
     fn from(doc: ParsedDocument) -> Self {
         Self {
             title: doc.title,
+            title_source: doc.title_source.map(|source| source.to_string()),
             sections: doc
                 .sections
                 .into_iter()
@@ -204,6 +734,28 @@ impl From<ParsedDocument> for PyParsedDocument {
                 .into_iter()
                 .map(PyChecklistItem::from)
                 .collect(),
+            links: doc.links.into_iter().map(PyParsedLink::from).collect(),
+            images: doc.images.into_iter().map(PyParsedLink::from).collect(),
+            wiki_links: doc.wiki_links.into_iter().map(PyWikiLink::from).collect(),
+            tags: doc.tags,
+            mentions: doc.mentions,
+            footnotes: doc
+                .footnotes
+                .into_iter()
+                .map(|(label, footnote)| (label, PyFootnote::from(footnote)))
+                .collect(),
+            inline_html: doc
+                .inline_html
+                .into_iter()
+                .map(PyHtmlFragment::from)
+                .collect(),
+            html_comments: doc
+                .html_comments
+                .into_iter()
+                .map(PyHtmlComment::from)
+                .collect(),
+            encoding: doc.encoding,
+            fields_inner: doc.fields,
             #[cfg(feature = "frontmatter")]
             frontmatter_inner: doc.frontmatter,
         }
@@ -223,19 +775,72 @@ pub struct PyParsedSection {
     /// Heading level (1-6) or None
     #[pyo3(get)]
     pub level: Option<u8>,
-    /// Raw content
+    /// Plain-text content
     #[pyo3(get)]
     pub content: String,
+    /// Markdown source as written
+    #[pyo3(get)]
+    pub raw: String,
     /// Order index
     #[pyo3(get)]
     pub order_idx: u32,
     /// Variables found in content
     #[pyo3(get)]
     pub variables: Vec<String>,
+    /// Source location
+    #[pyo3(get)]
+    pub span: PySourceSpan,
+    /// Variable occurrences with source locations
+    #[pyo3(get)]
+    pub variable_occurrences: Vec<PyVariableOccurrence>,
+    /// Structured table (table sections only)
+    #[pyo3(get)]
+    pub table: Option<PyParsedTable>,
+    /// Code block metadata (code sections only)
+    #[pyo3(get)]
+    pub code: Option<PyCodeBlockInfo>,
+    /// Item tree (list sections only)
+    #[pyo3(get)]
+    pub list: Option<PyParsedList>,
+    /// Options and selection (choice sections only)
+    #[pyo3(get)]
+    pub choice: Option<PyChoiceGroup>,
+    /// Callout metadata (callout blockquotes only)
+    #[pyo3(get)]
+    pub callout: Option<PyCallout>,
+    /// Heading anchor
+    #[pyo3(get)]
+    pub anchor: Option<String>,
+    /// Heading classes
+    #[pyo3(get)]
+    pub classes: Vec<String>,
+    /// Other heading attributes
+    #[pyo3(get)]
+    pub attributes: std::collections::BTreeMap<String, Option<String>>,
+    /// Custom type name (custom sections only)
+    #[pyo3(get)]
+    pub custom_type: Option<String>,
+    /// Notes added by section classifiers
+    #[pyo3(get)]
+    pub annotations: std::collections::BTreeMap<String, String>,
+    /// Inline tags (without the `#`)
+    #[pyo3(get)]
+    pub tags: Vec<String>,
+    /// Mentions (without the `@`)
+    #[pyo3(get)]
+    pub mentions: Vec<String>,
+    /// Inline fields by key
+    fields_inner: std::collections::BTreeMap<String, FieldValue>,
 }
 
 #[pymethods]
 impl PyParsedSection {
+    /// Get the inline fields as a dict
+    #[getter]
+    pub fn fields(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
+        fields_to_py(py, &self.fields_inner)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "ParsedSection(id={:?}, type={:?}, level={:?}, content={:?})",
@@ -258,68 +863,509 @@ impl From<ParsedSection> for PyParsedSection {
             section_type: section.section_type.as_str().to_string(),
             level: section.level,
             content: section.content,
+            raw: section.raw,
             order_idx: section.order_idx,
             variables: section.variables,
+            span: PySourceSpan::from(section.span),
+            variable_occurrences: section
+                .variable_occurrences
+                .into_iter()
+                .map(PyVariableOccurrence::from)
+                .collect(),
+            table: section.table.map(PyParsedTable::from),
+            code: section.code.map(PyCodeBlockInfo::from),
+            list: section.list.map(PyParsedList::from),
+            choice: section.choice.map(|inner| PyChoiceGroup { inner }),
+            callout: section.callout.map(PyCallout::from),
+            anchor: section.anchor,
+            classes: section.classes,
+            attributes: section.attributes,
+            custom_type: section.custom_type,
+            annotations: section.annotations,
+            tags: section.tags,
+            mentions: section.mentions,
+            fields_inner: section.fields,
         }
     }
 }
 
-/// Python wrapper for ChecklistItem
-#[pyclass(name = "ChecklistItem")]
+/// Python wrapper for ParsedList
+#[pyclass(name = "ParsedList")]
 #[derive(Clone)]
-pub struct PyChecklistItem {
-    /// Item text
+pub struct PyParsedList {
+    /// Whether the list is numbered
     #[pyo3(get)]
-    pub text: String,
-    /// Whether checked
+    pub ordered: bool,
+    /// First number of an ordered list
     #[pyo3(get)]
-    pub checked: bool,
-    /// Indentation level
+    pub start: Option<u64>,
+    /// Marker character
     #[pyo3(get)]
-    pub indent: u32,
-    /// AC references
+    pub marker: String,
+    /// Whether the list is tight
     #[pyo3(get)]
-    pub ac_refs: Vec<String>,
+    pub tight: bool,
+    /// Items in order
+    #[pyo3(get)]
+    pub items: Vec<PyListItem>,
 }
 
 #[pymethods]
-impl PyChecklistItem {
+impl PyParsedList {
     fn __repr__(&self) -> String {
-        let status = if self.checked { "x" } else { " " };
         format!(
-            "ChecklistItem([{}] {}, indent={})",
-            status, self.text, self.indent
+            "ParsedList(ordered={}, marker={:?}, items={})",
+            self.ordered,
+            self.marker,
+            self.items.len()
         )
     }
+
+    fn __len__(&self) -> usize {
+        self.items.len()
+    }
 }
 
-impl From<ChecklistItem> for PyChecklistItem {
-    fn from(item: ChecklistItem) -> Self {
+impl From<ParsedList> for PyParsedList {
+    fn from(list: ParsedList) -> Self {
         Self {
-            text: item.text,
-            checked: item.checked,
-            indent: item.indent,
-            ac_refs: item.ac_refs,
+            ordered: list.ordered,
+            start: list.start,
+            marker: list.marker.to_string(),
+            tight: list.tight,
+            items: list.items.into_iter().map(PyListItem::from).collect(),
         }
     }
 }
 
-/// Python wrapper for ChecklistSummary
-#[pyclass(name = "ChecklistSummary")]
+/// Python wrapper for ListItem
+#[pyclass(name = "ListItem")]
 #[derive(Clone)]
-pub struct PyChecklistSummary {
-    /// Total items
+pub struct PyListItem {
+    /// Plain text of the item
     #[pyo3(get)]
-    pub total: usize,
-    /// Completed items
+    pub text: String,
+    /// Task list state, if any
     #[pyo3(get)]
-    pub completed: usize,
-    /// Pending items
+    pub checked: Option<bool>,
+    /// Nested lists
     #[pyo3(get)]
-    pub pending: usize,
-    /// Completion percentage
+    pub children: Vec<PyParsedList>,
+    /// Source location
     #[pyo3(get)]
-    pub percentage: f64,
+    pub span: PySourceSpan,
+}
+
+#[pymethods]
+impl PyListItem {
+    fn __repr__(&self) -> String {
+        format!("ListItem({:?}, checked={:?})", self.text, self.checked)
+    }
+}
+
+impl From<ListItem> for PyListItem {
+    fn from(item: ListItem) -> Self {
+        Self {
+            text: item.text,
+            checked: item.checked,
+            children: item.children.into_iter().map(PyParsedList::from).collect(),
+            span: PySourceSpan::from(item.span),
+        }
+    }
+}
+
+/// Python wrapper for Callout
+#[pyclass(name = "Callout")]
+#[derive(Clone)]
+pub struct PyCallout {
+    /// Callout kind ("note", "warning", ...)
+    #[pyo3(get)]
+    pub kind: String,
+    /// Custom title
+    #[pyo3(get)]
+    pub title: Option<String>,
+    /// Whether the callout can be folded
+    #[pyo3(get)]
+    pub foldable: bool,
+    /// Whether the callout starts folded
+    #[pyo3(get)]
+    pub collapsed: bool,
+    /// Body text
+    #[pyo3(get)]
+    pub content: String,
+}
+
+#[pymethods]
+impl PyCallout {
+    fn __repr__(&self) -> String {
+        format!("Callout(kind={:?}, title={:?})", self.kind, self.title)
+    }
+}
+
+impl From<Callout> for PyCallout {
+    fn from(callout: Callout) -> Self {
+        Self {
+            kind: callout.kind.as_str().to_string(),
+            title: callout.title,
+            foldable: callout.foldable,
+            collapsed: callout.collapsed,
+            content: callout.content,
+        }
+    }
+}
+
+/// Python wrapper for ChoiceGroup
+#[pyclass(name = "ChoiceGroup")]
+#[derive(Clone)]
+pub struct PyChoiceGroup {
+    inner: ChoiceGroup,
+}
+
+#[pymethods]
+impl PyChoiceGroup {
+    /// Option texts in order
+    #[getter]
+    fn options(&self) -> Vec<String> {
+        self.inner.options.iter().map(|o| o.text.clone()).collect()
+    }
+
+    /// Indices of the selected options
+    #[getter]
+    fn selected(&self) -> Vec<usize> {
+        self.inner.selected.clone()
+    }
+
+    /// Whether more than one option may be selected
+    #[getter]
+    fn multi(&self) -> bool {
+        self.inner.multi
+    }
+
+    /// Whether a single-select group has more than one option selected
+    #[getter]
+    fn conflicting(&self) -> bool {
+        self.inner.is_conflicting()
+    }
+
+    /// Check that the selection is allowed by the group
+    ///
+    /// Raises:
+    ///     ValueError: If a single-select group has several options selected
+    fn validate(&self) -> PyResult<()> {
+        self.inner
+            .validate()
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Get the text of the selected options
+    fn selected_texts(&self) -> Vec<String> {
+        self.inner
+            .selected_texts()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Select the option at an index
+    fn select(&mut self, idx: usize) -> PyResult<()> {
+        self.inner
+            .select(idx)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Select the option with the given text
+    fn select_text(&mut self, text: &str) -> PyResult<()> {
+        self.inner
+            .select_text(text)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Deselect the option at an index
+    fn deselect(&mut self, idx: usize) -> PyResult<()> {
+        self.inner
+            .deselect(idx)
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))
+    }
+
+    /// Clear the selection
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    /// Render the group as Markdown with the current selection
+    fn to_markdown(&self) -> String {
+        self.inner.to_markdown()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ChoiceGroup(options={}, selected={:?}, multi={})",
+            self.inner.options.len(),
+            self.inner.selected,
+            self.inner.multi
+        )
+    }
+}
+
+/// Python wrapper for CodeBlockInfo
+#[pyclass(name = "CodeBlockInfo")]
+#[derive(Clone)]
+pub struct PyCodeBlockInfo {
+    /// "fenced" or "indented"
+    #[pyo3(get)]
+    pub kind: String,
+    /// Language from the info string
+    #[pyo3(get)]
+    pub language: Option<String>,
+    /// Full info string
+    #[pyo3(get)]
+    pub info: String,
+    /// Attributes parsed from the info string
+    #[pyo3(get)]
+    pub attributes: std::collections::BTreeMap<String, Option<String>>,
+}
+
+#[pymethods]
+impl PyCodeBlockInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "CodeBlockInfo(kind={:?}, language={:?})",
+            self.kind, self.language
+        )
+    }
+}
+
+impl From<CodeBlockInfo> for PyCodeBlockInfo {
+    fn from(info: CodeBlockInfo) -> Self {
+        Self {
+            kind: info.kind.as_str().to_string(),
+            language: info.language,
+            info: info.info,
+            attributes: info.attributes,
+        }
+    }
+}
+
+/// Python wrapper for ParsedTable
+#[pyclass(name = "ParsedTable")]
+#[derive(Clone)]
+pub struct PyParsedTable {
+    /// Header cell text
+    #[pyo3(get)]
+    pub headers: Vec<String>,
+    /// Body rows
+    #[pyo3(get)]
+    pub rows: Vec<Vec<String>>,
+    /// Column alignments ("none", "left", "center", "right")
+    #[pyo3(get)]
+    pub alignments: Vec<String>,
+}
+
+#[pymethods]
+impl PyParsedTable {
+    /// Get all body cells of the column with the given header
+    pub fn column(&self, header: &str) -> Option<Vec<String>> {
+        let idx = self.headers.iter().position(|h| h == header).or_else(|| {
+            self.headers
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(header))
+        })?;
+        Some(
+            self.rows
+                .iter()
+                .map(|row| row.get(idx).cloned().unwrap_or_default())
+                .collect(),
+        )
+    }
+
+    /// Get body rows as dicts keyed by header
+    pub fn records(&self, py: Python<'_>) -> PyResult<Vec<Py<PyDict>>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let dict = PyDict::new(py);
+                for (header, cell) in self.headers.iter().zip(row) {
+                    dict.set_item(header, cell)?;
+                }
+                Ok(dict.into())
+            })
+            .collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ParsedTable(headers={:?}, rows={})",
+            self.headers,
+            self.rows.len()
+        )
+    }
+}
+
+impl From<ParsedTable> for PyParsedTable {
+    fn from(table: ParsedTable) -> Self {
+        Self {
+            alignments: table
+                .alignments
+                .iter()
+                .map(|a| a.as_str().to_string())
+                .collect(),
+            headers: table.headers,
+            rows: table.rows,
+        }
+    }
+}
+
+/// Python wrapper for ChecklistItem
+#[pyclass(name = "ChecklistItem")]
+#[derive(Clone)]
+pub struct PyChecklistItem {
+    /// Item text
+    #[pyo3(get)]
+    pub text: String,
+    /// Whether checked
+    #[pyo3(get)]
+    pub checked: bool,
+    /// Indentation level
+    #[pyo3(get)]
+    pub indent: u32,
+    /// AC references
+    #[pyo3(get)]
+    pub ac_refs: Vec<String>,
+    /// Source location
+    #[pyo3(get)]
+    pub span: PySourceSpan,
+    /// Index of the containing section
+    #[pyo3(get)]
+    pub section_idx: Option<usize>,
+}
+
+#[pymethods]
+impl PyChecklistItem {
+    fn __repr__(&self) -> String {
+        let status = if self.checked { "x" } else { " " };
+        format!(
+            "ChecklistItem([{}] {}, indent={})",
+            status, self.text, self.indent
+        )
+    }
+}
+
+impl From<ChecklistItem> for PyChecklistItem {
+    fn from(item: ChecklistItem) -> Self {
+        Self {
+            text: item.text,
+            checked: item.checked,
+            indent: item.indent,
+            ac_refs: item.ac_refs,
+            span: PySourceSpan::from(item.span),
+            section_idx: item.section_idx,
+        }
+    }
+}
+
+/// Python wrapper for SourceSpan
+#[pyclass(name = "SourceSpan")]
+#[derive(Clone)]
+pub struct PySourceSpan {
+    /// Start byte offset
+    #[pyo3(get)]
+    pub byte_start: usize,
+    /// End byte offset (exclusive)
+    #[pyo3(get)]
+    pub byte_end: usize,
+    /// Start line (1-based)
+    #[pyo3(get)]
+    pub start_line: usize,
+    /// Start column (1-based)
+    #[pyo3(get)]
+    pub start_col: usize,
+    /// End line (1-based)
+    #[pyo3(get)]
+    pub end_line: usize,
+    /// End column (1-based, exclusive)
+    #[pyo3(get)]
+    pub end_col: usize,
+}
+
+#[pymethods]
+impl PySourceSpan {
+    fn __repr__(&self) -> String {
+        format!(
+            "SourceSpan({}..{}, {}:{}-{}:{})",
+            self.byte_start,
+            self.byte_end,
+            self.start_line,
+            self.start_col,
+            self.end_line,
+            self.end_col
+        )
+    }
+}
+
+impl From<SourceSpan> for PySourceSpan {
+    fn from(span: SourceSpan) -> Self {
+        Self {
+            byte_start: span.byte_range.start,
+            byte_end: span.byte_range.end,
+            start_line: span.start_line,
+            start_col: span.start_col,
+            end_line: span.end_line,
+            end_col: span.end_col,
+        }
+    }
+}
+
+/// Python wrapper for VariableOccurrence
+#[pyclass(name = "VariableOccurrence")]
+#[derive(Clone)]
+pub struct PyVariableOccurrence {
+    /// Variable name
+    #[pyo3(get)]
+    pub name: String,
+    /// Source location
+    #[pyo3(get)]
+    pub span: PySourceSpan,
+}
+
+#[pymethods]
+impl PyVariableOccurrence {
+    fn __repr__(&self) -> String {
+        format!(
+            "VariableOccurrence({:?}, line={}, col={})",
+            self.name, self.span.start_line, self.span.start_col
+        )
+    }
+}
+
+impl From<VariableOccurrence> for PyVariableOccurrence {
+    fn from(occurrence: VariableOccurrence) -> Self {
+        Self {
+            name: occurrence.name,
+            span: PySourceSpan::from(occurrence.span),
+        }
+    }
+}
+
+/// Python wrapper for ChecklistSummary
+#[pyclass(name = "ChecklistSummary")]
+#[derive(Clone)]
+pub struct PyChecklistSummary {
+    /// Total items
+    #[pyo3(get)]
+    pub total: usize,
+    /// Completed items
+    #[pyo3(get)]
+    pub completed: usize,
+    /// Pending items
+    #[pyo3(get)]
+    pub pending: usize,
+    /// Completion percentage
+    #[pyo3(get)]
+    pub percentage: f64,
 }
 
 #[pymethods]
@@ -353,6 +1399,223 @@ impl From<ChecklistSummary> for PyChecklistSummary {
     }
 }
 
+/// Python wrapper for ParsedLink
+#[pyclass(name = "ParsedLink")]
+#[derive(Clone)]
+pub struct PyParsedLink {
+    /// Destination URL
+    #[pyo3(get)]
+    pub url: String,
+    /// Optional title
+    #[pyo3(get)]
+    pub title: Option<String>,
+    /// Link text (alt text for images)
+    #[pyo3(get)]
+    pub text: String,
+    /// Link type ("inline", "reference", "autolink", "email")
+    #[pyo3(get)]
+    pub link_type: String,
+    /// Reference label for reference-style links
+    #[pyo3(get)]
+    pub reference: Option<String>,
+    /// Index of the containing section
+    #[pyo3(get)]
+    pub section_idx: Option<usize>,
+    /// Source location
+    #[pyo3(get)]
+    pub span: PySourceSpan,
+}
+
+#[pymethods]
+impl PyParsedLink {
+    fn __repr__(&self) -> String {
+        format!(
+            "ParsedLink({:?} -> {:?}, type={:?})",
+            self.text, self.url, self.link_type
+        )
+    }
+}
+
+impl From<ParsedLink> for PyParsedLink {
+    fn from(link: ParsedLink) -> Self {
+        Self {
+            url: link.url,
+            title: link.title,
+            text: link.text,
+            link_type: link.link_type.as_str().to_string(),
+            reference: link.reference,
+            section_idx: link.section_idx,
+            span: PySourceSpan::from(link.span),
+        }
+    }
+}
+
+/// Python wrapper for WikiLink
+#[pyclass(name = "WikiLink")]
+#[derive(Clone)]
+pub struct PyWikiLink {
+    /// Linked note or file (empty for links within the same note)
+    #[pyo3(get)]
+    pub target: String,
+    /// Heading anchor after `#`
+    #[pyo3(get)]
+    pub heading: Option<String>,
+    /// Block reference after `#^`
+    #[pyo3(get)]
+    pub block: Option<String>,
+    /// Display text after `|`
+    #[pyo3(get)]
+    pub alias: Option<String>,
+    /// Whether this is an embed (`![[...]]`)
+    #[pyo3(get)]
+    pub embed: bool,
+    /// Index of the containing section
+    #[pyo3(get)]
+    pub section_idx: Option<usize>,
+    /// Source location
+    #[pyo3(get)]
+    pub span: PySourceSpan,
+}
+
+#[pymethods]
+impl PyWikiLink {
+    fn __repr__(&self) -> String {
+        format!(
+            "WikiLink(target={:?}, heading={:?}, embed={})",
+            self.target, self.heading, self.embed
+        )
+    }
+}
+
+impl From<WikiLink> for PyWikiLink {
+    fn from(link: WikiLink) -> Self {
+        Self {
+            target: link.target,
+            heading: link.heading,
+            block: link.block,
+            alias: link.alias,
+            embed: link.embed,
+            section_idx: link.section_idx,
+            span: PySourceSpan::from(link.span),
+        }
+    }
+}
+
+/// Python wrapper for Footnote
+#[pyclass(name = "Footnote")]
+#[derive(Clone)]
+pub struct PyFootnote {
+    /// Footnote label
+    #[pyo3(get)]
+    pub label: String,
+    /// Definition content, or None if undefined
+    #[pyo3(get)]
+    pub content: Option<String>,
+    /// Index of the definition section
+    #[pyo3(get)]
+    pub definition_idx: Option<usize>,
+    /// Indices of sections that reference this footnote
+    #[pyo3(get)]
+    pub referencing_sections: Vec<usize>,
+}
+
+#[pymethods]
+impl PyFootnote {
+    fn __repr__(&self) -> String {
+        format!(
+            "Footnote({:?}, defined={}, references={})",
+            self.label,
+            self.definition_idx.is_some(),
+            self.referencing_sections.len()
+        )
+    }
+}
+
+impl From<Footnote> for PyFootnote {
+    fn from(footnote: Footnote) -> Self {
+        Self {
+            referencing_sections: footnote.referencing_sections(),
+            label: footnote.label,
+            content: footnote.content,
+            definition_idx: footnote.definition_idx,
+        }
+    }
+}
+
+/// Python wrapper for HtmlFragment
+#[pyclass(name = "HtmlFragment")]
+#[derive(Clone)]
+pub struct PyHtmlFragment {
+    /// HTML source of the fragment
+    #[pyo3(get)]
+    pub html: String,
+    /// Index of the containing section
+    #[pyo3(get)]
+    pub section_idx: Option<usize>,
+    /// Source location
+    #[pyo3(get)]
+    pub span: PySourceSpan,
+}
+
+#[pymethods]
+impl PyHtmlFragment {
+    fn __repr__(&self) -> String {
+        format!("HtmlFragment({:?})", self.html)
+    }
+}
+
+impl From<HtmlFragment> for PyHtmlFragment {
+    fn from(fragment: HtmlFragment) -> Self {
+        Self {
+            html: fragment.html,
+            section_idx: fragment.section_idx,
+            span: PySourceSpan::from(fragment.span),
+        }
+    }
+}
+
+/// Python wrapper for HtmlComment
+#[pyclass(name = "HtmlComment")]
+#[derive(Clone)]
+pub struct PyHtmlComment {
+    /// Comment text without delimiters
+    #[pyo3(get)]
+    pub text: String,
+    /// Whether the comment appeared inline
+    #[pyo3(get)]
+    pub inline: bool,
+    /// `(key, value)` if the comment is a `key: value` annotation
+    #[pyo3(get)]
+    pub annotation: Option<(String, String)>,
+    /// Index of the containing section
+    #[pyo3(get)]
+    pub section_idx: Option<usize>,
+    /// Source location
+    #[pyo3(get)]
+    pub span: PySourceSpan,
+}
+
+#[pymethods]
+impl PyHtmlComment {
+    fn __repr__(&self) -> String {
+        format!("HtmlComment({:?}, inline={})", self.text, self.inline)
+    }
+}
+
+impl From<HtmlComment> for PyHtmlComment {
+    fn from(comment: HtmlComment) -> Self {
+        Self {
+            annotation: comment
+                .annotation()
+                .map(|(key, value)| (key.to_string(), value.to_string())),
+            text: comment.text,
+            inline: comment.inline,
+            section_idx: comment.section_idx,
+            span: PySourceSpan::from(comment.span),
+        }
+    }
+}
+
 /// Python wrapper for ParsedEdge
 #[pyclass(name = "ParsedEdge")]
 #[derive(Clone)]
@@ -458,13 +1721,71 @@ fn yaml_value_to_py(py: Python<'_>, value: &serde_yaml::Value) -> PyResult<PyObj
     }
 }
 
+/// Convert inline fields to a Python dict, with dates as `datetime.date`
+fn fields_to_py(
+    py: Python<'_>,
+    fields: &std::collections::BTreeMap<String, FieldValue>,
+) -> PyResult<Py<PyDict>> {
+    let dict = PyDict::new(py);
+    for (key, value) in fields {
+        dict.set_item(key, field_value_to_py(py, value)?)?;
+    }
+    Ok(dict.into())
+}
+
+/// Convert an inline field value to a Python object
+fn field_value_to_py(py: Python<'_>, value: &FieldValue) -> PyResult<PyObject> {
+    use pyo3::types::{PyBool, PyDate, PyFloat, PyList, PyString};
+
+    match value {
+        FieldValue::Text(text) => Ok(PyString::new(py, text).into_any().unbind()),
+        FieldValue::Number(number) => Ok(PyFloat::new(py, *number).into_any().unbind()),
+        FieldValue::Bool(flag) => Ok(PyBool::new(py, *flag).to_owned().into_any().unbind()),
+        FieldValue::Date(date) => {
+            let parts: Vec<&str> = date.splitn(3, '-').collect();
+            let parsed = match parts[..] {
+                [year, month, day] => year
+                    .parse()
+                    .ok()
+                    .zip(month.parse().ok())
+                    .zip(day.parse().ok()),
+                _ => None,
+            };
+            // Dates like 2023-02-30 pass the pattern but are not real dates
+            match parsed.map(|((year, month), day)| PyDate::new(py, year, month, day)) {
+                Some(Ok(date)) => Ok(date.into_any().unbind()),
+                _ => Ok(PyString::new(py, date).into_any().unbind()),
+            }
+        }
+        FieldValue::List(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(field_value_to_py(py, item)?)?;
+            }
+            Ok(list.into_any().unbind())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Wrap a parser with default settings
+    fn parser() -> PyMarkdownParser {
+        parser_with(MarkdownParser::builder())
+    }
+
+    /// Wrap a parser configured with `builder`
+    fn parser_with(builder: crate::parser::MarkdownParserBuilder) -> PyMarkdownParser {
+        PyMarkdownParser {
+            inner: builder.build(),
+        }
+    }
+
     #[test]
     fn test_py_markdown_parser() {
-        let parser = PyMarkdownParser::new();
+        let parser = parser();
         let doc = parser.parse("# Test\n\nContent").unwrap();
 
         assert_eq!(doc.title, Some("Test".to_string()));
@@ -489,7 +1810,7 @@ mod tests {
 
     #[test]
     fn test_py_checklist_summary() {
-        let parser = PyMarkdownParser::new();
+        let parser = parser();
         let doc = parser.parse("- [ ] A\n- [x] B\n- [x] C").unwrap();
 
         let summary = doc.checklist_summary();
@@ -497,4 +1818,438 @@ mod tests {
         assert_eq!(summary.completed, 2);
         assert!((summary.percentage - 66.66666666666667).abs() < 0.001);
     }
+
+    #[test]
+    fn test_py_section_span() {
+        let parser = parser();
+        let doc = parser.parse("# Test\n\nHello {{name}}").unwrap();
+
+        let span = &doc.sections[1].span;
+        assert_eq!((span.byte_start, span.byte_end), (8, 22));
+        assert_eq!((span.start_line, span.start_col), (3, 1));
+        assert_eq!(doc.sections[1].variable_occurrences[0].name, "name");
+    }
+
+    #[test]
+    fn test_py_heading_hierarchy() {
+        let parser = parser();
+        let doc = parser.parse("# A\n\n## B\n\nText").unwrap();
+
+        assert_eq!(doc.parent_of(2), Some(1));
+        assert_eq!(doc.children_of(0), vec![1]);
+        assert_eq!(doc.heading_path(2), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_py_table() {
+        let parser = parser_with(MarkdownParser::builder().gfm());
+        let doc = parser.parse("| A | B |\n|---|--:|\n| 1 | 2 |").unwrap();
+
+        let table = doc.sections[0].table.as_ref().unwrap();
+        assert_eq!(table.headers, vec!["A", "B"]);
+        assert_eq!(table.alignments, vec!["none", "right"]);
+        assert_eq!(table.column("B"), Some(vec!["2".to_string()]));
+    }
+
+    #[test]
+    fn test_py_code_blocks() {
+        let parser = parser();
+        let doc = parser.parse("```rust linenos\nfn main() {}\n```").unwrap();
+
+        let blocks = doc.code_blocks("rust");
+        assert_eq!(blocks.len(), 1);
+        let code = blocks[0].code.as_ref().unwrap();
+        assert_eq!(code.kind, "fenced");
+        assert!(code.attributes.contains_key("linenos"));
+    }
+
+    #[test]
+    fn test_py_links() {
+        let parser = parser();
+        let doc = parser.parse("[a](https://a.example) ![b](b.png)").unwrap();
+
+        assert_eq!(doc.links.len(), 1);
+        assert_eq!(doc.links[0].link_type, "inline");
+        assert_eq!(doc.images[0].url, "b.png");
+    }
+
+    #[test]
+    fn test_py_footnotes() {
+        let parser = parser_with(MarkdownParser::builder().gfm());
+        let doc = parser.parse("A[^1] B[^2]\n\n[^1]: One").unwrap();
+
+        assert_eq!(doc.footnotes["1"].content.as_deref(), Some("One"));
+        assert_eq!(doc.dangling_footnotes()[0].label, "2");
+        assert!(doc.unused_footnotes().is_empty());
+    }
+
+    #[test]
+    fn test_py_html() {
+        let parser = parser();
+        let doc = parser
+            .parse("<!-- owner: team-a -->\n\nSee <kbd>Ctrl</kbd>.")
+            .unwrap();
+
+        assert_eq!(doc.sections[0].section_type, "html");
+        assert_eq!(doc.inline_html.len(), 2);
+        assert_eq!(
+            doc.html_comments[0].annotation,
+            Some(("owner".to_string(), "team-a".to_string()))
+        );
+        assert_eq!(doc.annotation("owner").as_deref(), Some("team-a"));
+    }
+
+    #[test]
+    fn test_py_list() {
+        let parser = parser_with(MarkdownParser::builder().gfm());
+        let doc = parser.parse("1. [x] One\n   - child\n2. Two").unwrap();
+
+        let list = doc.sections[0].list.as_ref().unwrap();
+        assert!(list.ordered);
+        assert_eq!(list.marker, ".");
+        assert_eq!(list.items[0].checked, Some(true));
+        assert_eq!(list.items[0].children[0].items[0].text, "child");
+    }
+
+    #[test]
+    fn test_py_checklist_sections() {
+        let parser = parser();
+        let doc = parser.parse("# Tasks\n\n- [ ] One\n- [x] Two").unwrap();
+
+        assert_eq!(doc.sections[1].section_type, "checklist");
+        assert_eq!(doc.checklist_items[0].section_idx, Some(1));
+        assert_eq!(doc.checklist_items_in(1).len(), 2);
+    }
+
+    #[test]
+    fn test_py_choice() {
+        let parser = parser();
+        let doc = parser.parse("- ( ) Small\n- (x) Large").unwrap();
+
+        assert_eq!(doc.sections[0].section_type, "choice");
+        let mut choice = doc.sections[0].choice.clone().unwrap();
+        assert_eq!(choice.options(), vec!["Small", "Large"]);
+        choice.select(0).unwrap();
+        assert_eq!(choice.selected(), vec![0]);
+        assert!(choice.select(9).is_err());
+
+        let doc = parser.parse("- (x) Small\n- (x) Large").unwrap();
+        let choice = doc.sections[0].choice.clone().unwrap();
+        assert!(choice.conflicting());
+        assert!(choice.validate().is_err());
+    }
+
+    #[test]
+    fn test_py_callout() {
+        let parser = parser();
+        let doc = parser.parse("> [!CAUTION]+ Careful\n> Hot.").unwrap();
+
+        let callout = doc.sections[0].callout.as_ref().unwrap();
+        assert_eq!(callout.kind, "caution");
+        assert!(callout.foldable && !callout.collapsed);
+        assert_eq!(callout.content, "Hot.");
+    }
+
+    #[test]
+    fn test_py_section_by_anchor() {
+        let parser = parser();
+        let doc = parser.parse("# Tasks / Subtasks\n\nBody").unwrap();
+
+        assert_eq!(doc.sections[0].anchor.as_deref(), Some("tasks--subtasks"));
+        assert!(doc.section_by_anchor("#tasks--subtasks").is_some());
+        assert!(doc.section_by_anchor("body").is_none());
+    }
+
+    #[test]
+    fn test_py_id_strategy() {
+        let parser = PyMarkdownParser::new(
+            false,
+            "heading_slug",
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        let doc = parser.parse("# Story\n\nBody").unwrap();
+        assert_eq!(doc.sections[1].id, "story/paragraph-1");
+
+        assert!(PyMarkdownParser::new(
+            false,
+            "sequential",
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_py_title_sources() {
+        let parser = PyMarkdownParser::new(
+            false,
+            "random",
+            Some(vec!["h1".to_string(), "heading".to_string()]),
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        let doc = parser.parse("## Notes\n\nBody").unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Notes"));
+        assert_eq!(doc.title_source.as_deref(), Some("heading"));
+
+        assert!(PyMarkdownParser::new(
+            false,
+            "random",
+            Some(vec!["h2".to_string()]),
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_py_iter_sections() {
+        let parser = parser_with(MarkdownParser::builder().id_strategy(IdStrategy::HeadingSlug));
+        let mut stream = parser.iter_sections("# One\n\n- [ ] Task\n\n# Two\n\nText".to_string());
+
+        let mut sections = Vec::new();
+        while let Some(section) = stream.__next__().unwrap() {
+            sections.push(section);
+        }
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[1].section.id, "one/checklist-1");
+        assert_eq!(sections[1].checklist_items[0].section_idx, Some(1));
+        assert_eq!(sections[3].section.span.start_line, 7);
+
+        assert!(parser.iter_file("/nonexistent/file.md").is_err());
+    }
+
+    #[test]
+    fn test_py_incremental_parser() {
+        let mut inc = PyIncrementalParser::new("# Title\n\nFirst\n", None).unwrap();
+        let changes = inc.edit(9, 14, "Second").unwrap();
+
+        assert_eq!(changes.modified, vec![1]);
+        assert_eq!(inc.source(), "# Title\n\nSecond\n");
+        assert_eq!(inc.document().sections[1].content, "Second");
+        assert!(inc.edit(5, 100, "").is_err());
+    }
+
+    #[test]
+    fn test_py_section_prefixes() {
+        let parser = PyMarkdownParser::new(
+            false,
+            "random",
+            None,
+            Some(vec![("**Decision:**".to_string(), "decision".to_string())]),
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        let doc = parser.parse("**Decision:** ship it\n\nOther").unwrap();
+        assert_eq!(doc.sections[0].section_type, "custom");
+        assert_eq!(doc.sections[0].custom_type.as_deref(), Some("decision"));
+        assert_eq!(doc.sections[1].custom_type, None);
+    }
+
+    #[test]
+    fn test_py_parse_many() {
+        let parser = parser();
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+        let simple = format!("{}/simple.md", fixtures);
+        let missing = format!("{}/missing.md", fixtures);
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let (docs, errors) = parser.parse_many(py, vec![simple.clone(), missing.clone()]);
+            assert_eq!(docs[&simple].title.as_deref(), Some("Simple Document"));
+            assert!(errors.contains_key(&missing));
+
+            let (docs, errors) = parser.parse_dir(py, fixtures, "simple*.md").unwrap();
+            assert_eq!(docs.len(), 1);
+            assert!(errors.is_empty());
+            assert!(parser.parse_dir(py, fixtures, "***").is_err());
+        });
+    }
+
+    #[test]
+    fn test_py_parse_bytes() {
+        let parser = parser();
+        let doc = parser
+            .parse_bytes(b"\xEF\xBB\xBF# Title\r\n\r\n- [ ] Task\r\n")
+            .unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Title"));
+        assert_eq!(doc.checklist_items[0].text, "Task");
+        assert_eq!(doc.encoding.as_deref(), Some("UTF-8"));
+        assert_eq!(doc.checklist_items[0].span.byte_start, 14);
+        assert!(parser.parse_bytes(b"\xFF\xFE#").is_err());
+
+        let parser = parser_with(MarkdownParser::builder().normalize_line_endings(true));
+        let doc = parser
+            .parse_bytes(b"# Title\r\n\r\n- [ ] Task\r\n")
+            .unwrap();
+        assert_eq!(doc.checklist_items[0].span.byte_start, 9);
+
+        let fallback = PyMarkdownParser::new(
+            false,
+            "random",
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            Some("cp1251".to_string()),
+        );
+        assert_eq!(fallback.is_ok(), cfg!(feature = "encoding"));
+    }
+
+    #[test]
+    fn test_py_wiki_links() {
+        let parser = parser();
+        let doc = parser
+            .parse("# Notes\n\nSee ![[Design#^goals|Goals]]")
+            .unwrap();
+        let link = &doc.wiki_links[0];
+        assert_eq!(link.target, "Design");
+        assert_eq!(link.block.as_deref(), Some("goals"));
+        assert_eq!(link.alias.as_deref(), Some("Goals"));
+        assert!(link.embed);
+        assert_eq!(link.section_idx, Some(1));
+    }
+
+    #[test]
+    fn test_py_tags_and_mentions() {
+        let parser = parser();
+        let doc = parser
+            .parse("# Plan #area/infra\n\nOwned by @alice, see `#not-a-tag`")
+            .unwrap();
+        assert_eq!(doc.sections[0].tags, vec!["area/infra"]);
+        assert_eq!(doc.sections[1].mentions, vec!["alice"]);
+        assert!(doc.tags.contains("area/infra"));
+        assert_eq!(doc.tags.len(), 1);
+        assert!(doc.mentions.contains("alice"));
+    }
+
+    #[test]
+    fn test_py_inline_fields() {
+        pyo3::prepare_freethreaded_python();
+        let parser = parser_with(MarkdownParser::builder().coerce_fields(true));
+        let doc = parser
+            .parse("**Status:** Draft\n\npoints:: 3\ndue:: 2024-05-01\nlabels:: [api, true]")
+            .unwrap();
+
+        Python::with_gil(|py| {
+            let fields = doc.fields(py).unwrap().into_bound(py);
+            let get = |key: &str| fields.get_item(key).unwrap().unwrap();
+            assert_eq!(get("Status").extract::<String>().unwrap(), "Draft");
+            assert_eq!(get("points").extract::<f64>().unwrap(), 3.0);
+            assert_eq!(get("due").get_type().name().unwrap(), "date");
+            let labels = get("labels");
+            assert_eq!(
+                labels.get_item(0).unwrap().extract::<String>().unwrap(),
+                "api"
+            );
+            assert!(labels.get_item(1).unwrap().extract::<bool>().unwrap());
+
+            let section = doc.sections[1].fields(py).unwrap().into_bound(py);
+            assert_eq!(section.len(), 3);
+        });
+    }
+
+    #[test]
+    fn test_py_extension_options() {
+        let table = "| A |\n|---|\n| 1 |\n\n~~old~~";
+        let parser = PyMarkdownParser::new(
+            false,
+            "random",
+            None,
+            None,
+            false,
+            Some(true),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        let doc = parser.parse(table).unwrap();
+        assert_eq!(doc.sections[0].section_type, "table");
+        assert_eq!(doc.sections[1].content, "~~old~~");
+
+        // Explicit options override the gfm preset
+        let parser = PyMarkdownParser::new(
+            true,
+            "random",
+            None,
+            None,
+            false,
+            Some(false),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        let doc = parser.parse(table).unwrap();
+        assert_eq!(doc.sections[0].section_type, "paragraph");
+        assert_eq!(doc.sections[1].content, "old");
+    }
 }
@@ -1,7 +1,19 @@
 //! Section types for parsed Markdown documents
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
 use uuid::Uuid;
 
+use crate::callout::Callout;
+use crate::choice::ChoiceGroup;
+use crate::code::CodeBlockInfo;
+use crate::fields::FieldValue;
+use crate::list::ParsedList;
+use crate::span::SourceSpan;
+use crate::table::ParsedTable;
+use crate::variables::VariableOccurrence;
+
 /// Type of Markdown section
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -22,8 +34,14 @@ pub enum SectionType {
     HorizontalRule,
     /// Checklist (task list)
     Checklist,
-    /// Choice/selection
+    /// Choice/selection (`- ( )` radio items or a list after `<!-- choice -->`)
     Choice,
+    /// Footnote definition (`[^label]: text`)
+    FootnoteDefinition,
+    /// Raw HTML block
+    Html,
+    /// Type assigned by a `SectionClassifier` (name in `custom_type`)
+    Custom,
 }
 
 impl SectionType {
@@ -39,6 +57,9 @@ impl SectionType {
             SectionType::HorizontalRule => "hr",
             SectionType::Checklist => "checklist",
             SectionType::Choice => "choice",
+            SectionType::FootnoteDefinition => "footnote",
+            SectionType::Html => "html",
+            SectionType::Custom => "custom",
         }
     }
 }
@@ -59,12 +80,61 @@ pub struct ParsedSection {
     pub section_type: SectionType,
     /// Heading level (1-6) if this is a heading, None otherwise
     pub level: Option<u8>,
-    /// Raw content of the section
+    /// Plain-text content of the section, with Markdown syntax and inline
+    /// formatting removed
     pub content: String,
+    /// Markdown source of the section exactly as written
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub raw: String,
     /// Zero-based index indicating section order in document
     pub order_idx: u32,
     /// Variable names found in this section's content
     pub variables: Vec<String>,
+    /// Location of this section in the source text
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub span: SourceSpan,
+    /// Every variable occurrence in this section with its location
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variable_occurrences: Vec<VariableOccurrence>,
+    /// Structured cells for table sections
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub table: Option<ParsedTable>,
+    /// Language and info string metadata for code sections
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub code: Option<CodeBlockInfo>,
+    /// Item tree for list sections
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub list: Option<ParsedList>,
+    /// Options and selection for choice sections
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub choice: Option<ChoiceGroup>,
+    /// Kind, title and body for blockquotes written as callouts
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub callout: Option<Callout>,
+    /// GitHub-style anchor for headings, or the explicit `{#id}`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub anchor: Option<String>,
+    /// Heading classes from `{.class}` attributes
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub classes: Vec<String>,
+    /// Other heading attributes from `{key=value}`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub attributes: BTreeMap<String, Option<String>>,
+    /// Name of the custom type when `section_type` is `Custom`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub custom_type: Option<String>,
+    /// Key/value notes added by a `SectionClassifier`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub annotations: BTreeMap<String, String>,
+    /// Inline `#tags` (without the `#`), outside code and links
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tags: Vec<String>,
+    /// `@mentions` (without the `@`), outside code and links
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mentions: Vec<String>,
+    /// Inline fields (`Key:: value`, `**Key:** value`) by key
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fields: BTreeMap<String, FieldValue>,
 }
 
 impl ParsedSection {
@@ -75,8 +145,24 @@ impl ParsedSection {
             section_type,
             level: None,
             content,
+            raw: String::new(),
             order_idx,
             variables: Vec::new(),
+            span: SourceSpan::default(),
+            variable_occurrences: Vec::new(),
+            table: None,
+            code: None,
+            list: None,
+            choice: None,
+            callout: None,
+            anchor: None,
+            classes: Vec::new(),
+            attributes: BTreeMap::new(),
+            custom_type: None,
+            annotations: BTreeMap::new(),
+            tags: Vec::new(),
+            mentions: Vec::new(),
+            fields: BTreeMap::new(),
         }
     }
 
@@ -87,8 +173,24 @@ impl ParsedSection {
             section_type,
             level: None,
             content,
+            raw: String::new(),
             order_idx,
             variables: Vec::new(),
+            span: SourceSpan::default(),
+            variable_occurrences: Vec::new(),
+            table: None,
+            code: None,
+            list: None,
+            choice: None,
+            callout: None,
+            anchor: None,
+            classes: Vec::new(),
+            attributes: BTreeMap::new(),
+            custom_type: None,
+            annotations: BTreeMap::new(),
+            tags: Vec::new(),
+            mentions: Vec::new(),
+            fields: BTreeMap::new(),
         }
     }
 
@@ -103,6 +205,184 @@ impl ParsedSection {
         self.variables = variables;
         self
     }
+
+    /// Set the Markdown source of this section
+    pub fn with_raw(mut self, raw: String) -> Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Set the source location of this section
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = span;
+        self
+    }
+}
+
+/// A parsed section that borrows its text from the source
+///
+/// Produced by [`MarkdownParser::parse_ref`](crate::MarkdownParser::parse_ref).
+/// `raw` is always a slice of the input; `content` and `variables` borrow
+/// from it when the plain text is a single contiguous run of the source (a
+/// one-line paragraph or heading, for example) and are owned otherwise.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ParsedSectionRef<'a> {
+    /// Unique identifier for this section
+    pub id: String,
+    /// Type of section
+    pub section_type: SectionType,
+    /// Heading level (1-6) if this is a heading, None otherwise
+    pub level: Option<u8>,
+    /// Plain-text content of the section
+    pub content: Cow<'a, str>,
+    /// Markdown source of the section exactly as written
+    pub raw: &'a str,
+    /// Zero-based index indicating section order in document
+    pub order_idx: u32,
+    /// Variable names found in this section's content
+    pub variables: Vec<Cow<'a, str>>,
+    /// Location of this section in the source text
+    pub span: SourceSpan,
+    /// Every variable occurrence in this section with its location
+    pub variable_occurrences: Vec<VariableOccurrence>,
+    /// Structured cells for table sections
+    pub table: Option<ParsedTable>,
+    /// Language and info string metadata for code sections
+    pub code: Option<CodeBlockInfo>,
+    /// Item tree for list sections
+    pub list: Option<ParsedList>,
+    /// Options and selection for choice sections
+    pub choice: Option<ChoiceGroup>,
+    /// Kind, title and body for blockquotes written as callouts
+    pub callout: Option<Callout>,
+    /// GitHub-style anchor for headings, or the explicit `{#id}`
+    pub anchor: Option<String>,
+    /// Heading classes from `{.class}` attributes
+    pub classes: Vec<String>,
+    /// Other heading attributes from `{key=value}`
+    pub attributes: BTreeMap<String, Option<String>>,
+    /// Name of the custom type when `section_type` is `Custom`
+    pub custom_type: Option<String>,
+    /// Key/value notes added by a `SectionClassifier`
+    pub annotations: BTreeMap<String, String>,
+    /// Inline `#tags` (without the `#`), outside code and links
+    pub tags: Vec<&'a str>,
+    /// `@mentions` (without the `@`), outside code and links
+    pub mentions: Vec<&'a str>,
+    /// Inline fields (`Key:: value`, `**Key:** value`) by key
+    pub fields: BTreeMap<String, FieldValue>,
+}
+
+impl ParsedSectionRef<'_> {
+    /// Copy the borrowed text into an owned `ParsedSection`
+    pub fn into_owned(self) -> ParsedSection {
+        ParsedSection {
+            id: self.id,
+            section_type: self.section_type,
+            level: self.level,
+            content: self.content.into_owned(),
+            raw: self.raw.to_string(),
+            order_idx: self.order_idx,
+            variables: self.variables.into_iter().map(Cow::into_owned).collect(),
+            span: self.span,
+            variable_occurrences: self.variable_occurrences,
+            table: self.table,
+            code: self.code,
+            list: self.list,
+            choice: self.choice,
+            callout: self.callout,
+            anchor: self.anchor,
+            classes: self.classes,
+            attributes: self.attributes,
+            custom_type: self.custom_type,
+            annotations: self.annotations,
+            tags: self.tags.into_iter().map(String::from).collect(),
+            mentions: self.mentions.into_iter().map(String::from).collect(),
+            fields: self.fields,
+        }
+    }
+}
+
+/// Read access shared by owned and borrowed sections
+pub(crate) trait SectionView {
+    fn section_type(&self) -> SectionType;
+    fn level(&self) -> Option<u8>;
+    fn content(&self) -> &str;
+    fn raw(&self) -> &str;
+    fn span(&self) -> &SourceSpan;
+    fn anchor(&self) -> Option<&str>;
+    fn set_id(&mut self, id: String);
+    /// The section as a `ParsedSection`, copied if it is borrowed
+    fn to_owned_section(&self) -> Cow<'_, ParsedSection>;
+}
+
+impl SectionView for ParsedSection {
+    fn section_type(&self) -> SectionType {
+        self.section_type
+    }
+
+    fn level(&self) -> Option<u8> {
+        self.level
+    }
+
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    fn span(&self) -> &SourceSpan {
+        &self.span
+    }
+
+    fn anchor(&self) -> Option<&str> {
+        self.anchor.as_deref()
+    }
+
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    fn to_owned_section(&self) -> Cow<'_, ParsedSection> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl SectionView for ParsedSectionRef<'_> {
+    fn section_type(&self) -> SectionType {
+        self.section_type
+    }
+
+    fn level(&self) -> Option<u8> {
+        self.level
+    }
+
+    fn content(&self) -> &str {
+        &self.content
+    }
+
+    fn raw(&self) -> &str {
+        self.raw
+    }
+
+    fn span(&self) -> &SourceSpan {
+        &self.span
+    }
+
+    fn anchor(&self) -> Option<&str> {
+        self.anchor.as_deref()
+    }
+
+    fn set_id(&mut self, id: String) {
+        self.id = id;
+    }
+
+    fn to_owned_section(&self) -> Cow<'_, ParsedSection> {
+        Cow::Owned(self.clone().into_owned())
+    }
 }
 
 #[cfg(test)]
@@ -117,9 +397,12 @@ mod tests {
         assert_eq!(SectionType::Code.as_str(), "code");
         assert_eq!(SectionType::Table.as_str(), "table");
         assert_eq!(SectionType::Blockquote.as_str(), "blockquote");
+        assert_eq!(SectionType::Html.as_str(), "html");
         assert_eq!(SectionType::HorizontalRule.as_str(), "hr");
         assert_eq!(SectionType::Checklist.as_str(), "checklist");
         assert_eq!(SectionType::Choice.as_str(), "choice");
+        assert_eq!(SectionType::FootnoteDefinition.as_str(), "footnote");
+        assert_eq!(SectionType::Custom.as_str(), "custom");
     }
 
     #[test]
@@ -137,15 +420,18 @@ mod tests {
         assert_eq!(section.order_idx, 0);
         assert!(section.level.is_none());
         assert!(section.variables.is_empty());
+        assert!(section.raw.is_empty());
     }
 
     #[test]
     fn test_parsed_section_builder() {
         let section = ParsedSection::new(SectionType::Heading, "Title".to_string(), 0)
             .with_level(1)
-            .with_variables(vec!["name".to_string()]);
+            .with_variables(vec!["name".to_string()])
+            .with_raw("# Title".to_string());
 
         assert_eq!(section.level, Some(1));
+        assert_eq!(section.raw, "# Title");
         assert_eq!(section.variables, vec!["name"]);
     }
 }
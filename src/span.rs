@@ -0,0 +1,138 @@
+//! Source location tracking for parsed elements
+
+use std::ops::Range;
+
+/// Location of a parsed element in the source text
+///
+/// Byte offsets are relative to the full input passed to the parser
+/// (including any frontmatter). Lines and columns are 1-based; columns
+/// count characters, not bytes. The end position is exclusive.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceSpan {
+    /// Byte range in the source text
+    pub byte_range: Range<usize>,
+    /// Line of the first character (1-based)
+    pub start_line: usize,
+    /// Column of the first character (1-based)
+    pub start_col: usize,
+    /// Line of the end position (1-based)
+    pub end_line: usize,
+    /// Column of the end position (1-based, exclusive)
+    pub end_col: usize,
+}
+
+impl SourceSpan {
+    /// Length of the span in bytes
+    pub fn len(&self) -> usize {
+        self.byte_range.len()
+    }
+
+    /// Check if the span covers no bytes
+    pub fn is_empty(&self) -> bool {
+        self.byte_range.is_empty()
+    }
+
+    /// Check if the span contains a byte offset
+    pub fn contains(&self, offset: usize) -> bool {
+        self.byte_range.contains(&offset)
+    }
+
+    /// Move the span by `bytes` bytes and `lines` whole lines
+    pub(crate) fn shift(&mut self, bytes: isize, lines: isize) {
+        self.byte_range = self.byte_range.start.saturating_add_signed(bytes)
+            ..self.byte_range.end.saturating_add_signed(bytes);
+        self.start_line = self.start_line.saturating_add_signed(lines);
+        self.end_line = self.end_line.saturating_add_signed(lines);
+    }
+}
+
+/// Maps byte offsets to line and column positions
+pub(crate) struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Build an index of line start offsets for `source`
+    pub(crate) fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            source
+                .bytes()
+                .enumerate()
+                .filter(|(_, b)| *b == b'\n')
+                .map(|(i, _)| i + 1),
+        );
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// Get the 1-based (line, column) of a byte offset
+    pub(crate) fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.source.len());
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let col = self
+            .source
+            .get(line_start..offset)
+            .map(|s| s.chars().count())
+            .unwrap_or(offset - line_start);
+        (line + 1, col + 1)
+    }
+
+    /// Build a span for a byte range
+    pub(crate) fn span(&self, byte_range: Range<usize>) -> SourceSpan {
+        let (start_line, start_col) = self.position(byte_range.start);
+        let (end_line, end_col) = self.position(byte_range.end);
+        SourceSpan {
+            byte_range,
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_first_line() {
+        let index = LineIndex::new("hello\nworld");
+        assert_eq!(index.position(0), (1, 1));
+        assert_eq!(index.position(4), (1, 5));
+    }
+
+    #[test]
+    fn test_position_later_lines() {
+        let index = LineIndex::new("hello\nworld\n\nend");
+        assert_eq!(index.position(6), (2, 1));
+        assert_eq!(index.position(11), (2, 6));
+        assert_eq!(index.position(12), (3, 1));
+        assert_eq!(index.position(13), (4, 1));
+    }
+
+    #[test]
+    fn test_position_counts_chars() {
+        let index = LineIndex::new("héllo wörld");
+        // 'w' is preceded by 6 characters but 7 bytes
+        assert_eq!(index.position(8), (1, 8));
+    }
+
+    #[test]
+    fn test_span() {
+        let index = LineIndex::new("# Title\n\nBody text");
+        let span = index.span(9..18);
+        assert_eq!(span.byte_range, 9..18);
+        assert_eq!((span.start_line, span.start_col), (3, 1));
+        assert_eq!((span.end_line, span.end_col), (3, 10));
+        assert_eq!(span.len(), 9);
+        assert!(span.contains(9));
+        assert!(!span.contains(18));
+    }
+}
@@ -0,0 +1,400 @@
+//! Streaming section iterator for large documents
+//!
+//! The input is split into chunks at top-level ATX headings (`# ...` at
+//! column 0), never inside fenced code, HTML blocks, comments or
+//! frontmatter. Each chunk is parsed on its own, so memory use is bounded by
+//! the largest chunk rather than the whole document.
+//!
+//! Sections, anchors, IDs, spans and checklist items match what
+//! [`MarkdownParser::parse`] produces. Reference links and footnotes only
+//! resolve when their definition is in the same chunk.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use crate::anchor::Slugger;
+use crate::checklist::ChecklistItem;
+use crate::error::ParseError;
+use crate::id::IdAssigner;
+use crate::list::ParsedList;
+use crate::parser::MarkdownParser;
+use crate::section::ParsedSection;
+
+/// HTML block tags whose content runs to a closing tag rather than a blank line
+const RAW_HTML_TAGS: [&str; 4] = ["pre", "script", "style", "textarea"];
+
+/// A section produced by [`SectionStream`] with the checklist items it contains
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamedSection {
+    /// The section, with its variables
+    pub section: ParsedSection,
+    /// Checklist items inside the section
+    pub checklist_items: Vec<ChecklistItem>,
+}
+
+/// Iterator over the sections of a Markdown document, parsed chunk by chunk
+///
+/// Created with [`MarkdownParser::parse_iter`] or
+/// [`MarkdownParser::parse_reader`].
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{MarkdownParser, SectionType};
+///
+/// let parser = MarkdownParser::new();
+/// let content = "# v2.0\n\n- [x] Ship it\n\n# v1.0\n\nFirst {{release}}";
+///
+/// let sections: Vec<_> = parser.parse_iter(content).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(sections.len(), 4);
+///
+/// assert_eq!(sections[1].section.section_type, SectionType::Checklist);
+/// assert_eq!(sections[1].checklist_items[0].text, "Ship it");
+/// assert_eq!(sections[1].checklist_items[0].section_idx, Some(1));
+///
+/// assert_eq!(sections[3].section.variables, vec!["release"]);
+/// assert_eq!(sections[3].section.span.start_line, 7);
+/// ```
+pub struct SectionStream<R> {
+    parser: MarkdownParser,
+    reader: R,
+    scanner: ChunkScanner,
+    /// Heading line that starts the next chunk
+    pending: String,
+    /// Whether the reader is exhausted
+    done: bool,
+    /// Byte offset of the next chunk
+    offset: usize,
+    /// Number of lines before the next chunk
+    lines: usize,
+    /// Number of sections produced so far
+    produced: usize,
+    slugger: Slugger,
+    ids: IdAssigner,
+    ready: VecDeque<StreamedSection>,
+}
+
+impl<R: BufRead> SectionStream<R> {
+    /// Stream the sections read from `reader` using `parser`'s settings
+    pub fn new(parser: MarkdownParser, reader: R) -> Self {
+        let ids = IdAssigner::new(parser.id_strategy().clone());
+        Self {
+            parser,
+            reader,
+            scanner: ChunkScanner::default(),
+            pending: String::new(),
+            done: false,
+            offset: 0,
+            lines: 0,
+            produced: 0,
+            slugger: Slugger::default(),
+            ids,
+            ready: VecDeque::new(),
+        }
+    }
+
+    /// Read lines up to the next chunk boundary
+    fn read_chunk(&mut self) -> Result<Option<String>, ParseError> {
+        let mut chunk = std::mem::take(&mut self.pending);
+        while !self.done {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                self.done = true;
+            } else if self.scanner.is_boundary(&line) && !chunk.is_empty() {
+                self.pending = line;
+                break;
+            } else {
+                chunk.push_str(&line);
+            }
+        }
+        Ok((!chunk.is_empty()).then_some(chunk))
+    }
+
+    /// Parse a chunk and queue its sections
+    fn push_chunk(&mut self, chunk: &str) -> Result<(), ParseError> {
+        let doc = self
+            .parser
+            .parse_without_ids(chunk, None, &mut self.slugger)?;
+        let mut sections = doc.sections;
+
+        let (bytes, lines) = (self.offset as isize, self.lines as isize);
+        for (idx, section) in sections.iter_mut().enumerate() {
+            shift_section(section, bytes, lines);
+            section.order_idx = (self.produced + idx) as u32;
+        }
+        self.ids.assign(&mut sections);
+
+        let mut items: Vec<Vec<ChecklistItem>> = vec![Vec::new(); sections.len()];
+        for mut item in doc.checklist_items {
+            if let Some(idx) = item.section_idx {
+                item.span.shift(bytes, lines);
+                item.section_idx = Some(self.produced + idx);
+                items[idx].push(item);
+            }
+        }
+
+        self.produced += sections.len();
+        self.offset += chunk.len();
+        self.lines += chunk.matches('\n').count();
+        self.ready.extend(
+            sections
+                .into_iter()
+                .zip(items)
+                .map(|(section, checklist_items)| StreamedSection {
+                    section,
+                    checklist_items,
+                }),
+        );
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for SectionStream<R> {
+    type Item = Result<StreamedSection, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(section) = self.ready.pop_front() {
+                return Some(Ok(section));
+            }
+            let result = match self.read_chunk() {
+                Ok(Some(chunk)) => self.push_chunk(&chunk),
+                Ok(None) => return None,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                // Stop after the first error
+                self.done = true;
+                self.pending.clear();
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+/// Move all spans in a section by a chunk's position in the document
+pub(crate) fn shift_section(section: &mut ParsedSection, bytes: isize, lines: isize) {
+    section.span.shift(bytes, lines);
+    for occurrence in &mut section.variable_occurrences {
+        occurrence.span.shift(bytes, lines);
+    }
+    if let Some(list) = &mut section.list {
+        shift_list(list, bytes, lines);
+    }
+    if let Some(choice) = &mut section.choice {
+        for option in &mut choice.options {
+            option.span.shift(bytes, lines);
+        }
+    }
+}
+
+fn shift_list(list: &mut ParsedList, bytes: isize, lines: isize) {
+    for item in &mut list.items {
+        item.span.shift(bytes, lines);
+        for child in &mut item.children {
+            shift_list(child, bytes, lines);
+        }
+    }
+}
+
+/// Tracks the block context of each line to find safe chunk boundaries
+#[derive(Debug, Default)]
+pub(crate) struct ChunkScanner {
+    /// Whether a non-blank line has been seen
+    started: bool,
+    in_frontmatter: bool,
+    /// Open code fence character and length
+    fence: Option<(char, usize)>,
+    in_comment: bool,
+    /// Inside an HTML block that ends at a blank line
+    in_html: bool,
+    /// Inside a `<pre>`-style HTML block, with its closing tag
+    raw_close: Option<String>,
+}
+
+impl ChunkScanner {
+    /// Create a scanner for lines that follow other content
+    pub(crate) fn after_content() -> Self {
+        Self {
+            started: true,
+            ..Self::default()
+        }
+    }
+
+    /// Check whether `line` starts a new chunk, updating the block context
+    pub(crate) fn is_boundary(&mut self, line: &str) -> bool {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let blank = text.trim().is_empty();
+        let first = !self.started && !blank;
+        self.started |= !blank;
+
+        if self.in_frontmatter {
+            self.in_frontmatter = !text.starts_with("---");
+            return false;
+        }
+        let leading = text.trim_start_matches('\u{feff}').trim_start();
+        if first && cfg!(feature = "frontmatter") && leading.starts_with("---") {
+            self.in_frontmatter = true;
+            return false;
+        }
+        if let Some((ch, len)) = self.fence {
+            let trimmed = text.trim_start_matches(' ');
+            let run = trimmed.chars().take_while(|&c| c == ch).count();
+            if text.len() - trimmed.len() <= 3 && run >= len && trimmed[run..].trim().is_empty() {
+                self.fence = None;
+            }
+            return false;
+        }
+        if let Some(close) = &self.raw_close {
+            if text.to_ascii_lowercase().contains(close.as_str()) {
+                self.raw_close = None;
+            }
+            return false;
+        }
+        if self.in_comment {
+            self.in_comment = !text.contains("-->");
+            return false;
+        }
+        if self.in_html {
+            self.in_html = !blank;
+            return false;
+        }
+
+        let trimmed = text.trim_start_matches(' ');
+        if text.len() - trimmed.len() <= 3 {
+            for ch in ['`', '~'] {
+                let run = trimmed.chars().take_while(|&c| c == ch).count();
+                if run >= 3 {
+                    self.fence = Some((ch, run));
+                    return false;
+                }
+            }
+            if let Some(tag) = trimmed.strip_prefix('<') {
+                let tag = tag.to_ascii_lowercase();
+                if let Some(name) = RAW_HTML_TAGS.iter().find(|name| tag.starts_with(*name)) {
+                    let close = format!("</{}>", name);
+                    if !tag.contains(&close) {
+                        self.raw_close = Some(close);
+                    }
+                    return false;
+                }
+                if !tag.starts_with("!--") {
+                    self.in_html = true;
+                    return false;
+                }
+            }
+        }
+        if let Some(pos) = text.rfind("<!--") {
+            self.in_comment = !text[pos..].contains("-->");
+        }
+
+        is_atx_heading(text)
+    }
+}
+
+/// Check for a heading line starting at column 0
+fn is_atx_heading(text: &str) -> bool {
+    let hashes = text.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&hashes)
+        && text[hashes..]
+            .chars()
+            .next()
+            .is_none_or(|c| c == ' ' || c == '\t')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id::IdStrategy;
+    use std::io::Cursor;
+
+    fn assert_matches_parse(parser: &MarkdownParser, content: &str) {
+        let doc = parser.parse(content).unwrap();
+        let streamed: Vec<StreamedSection> = parser
+            .parse_reader(Cursor::new(content))
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(streamed.len(), doc.sections.len());
+        for (streamed, section) in streamed.iter().zip(&doc.sections) {
+            let s = &streamed.section;
+            assert_eq!(s.section_type, section.section_type);
+            assert_eq!(s.content, section.content);
+            assert_eq!(s.id, section.id);
+            assert_eq!(s.anchor, section.anchor);
+            assert_eq!(s.order_idx, section.order_idx);
+            assert_eq!(s.span, section.span);
+            assert_eq!(s.list, section.list);
+            assert_eq!(s.variable_occurrences, section.variable_occurrences);
+        }
+
+        let items: Vec<ChecklistItem> = streamed
+            .into_iter()
+            .flat_map(|s| s.checklist_items)
+            .collect();
+        let linked: Vec<&ChecklistItem> = doc
+            .checklist_items
+            .iter()
+            .filter(|i| i.section_idx.is_some())
+            .collect();
+        assert_eq!(items.iter().collect::<Vec<_>>(), linked);
+    }
+
+    #[test]
+    fn test_matches_full_parse() {
+        let content = "# Changelog\n\nIntro {{version}}\n\n## Notes\n\n- [x] Done\n  - [ ] Nested\n\n\
+                       ```sh\n# not a heading\n```\n\n## Notes\n\n1. one\n2. two\n\n# Older\n\nText\r\n";
+        for strategy in [IdStrategy::ContentHash, IdStrategy::HeadingSlug] {
+            let parser = MarkdownParser::builder().id_strategy(strategy).build();
+            assert_matches_parse(&parser, content);
+        }
+    }
+
+    #[test]
+    fn test_no_split_inside_blocks() {
+        let mut scanner = ChunkScanner::default();
+        let lines = [
+            "<!-- start",
+            "# in comment",
+            "-->",
+            "<div>",
+            "# in html",
+            "",
+            "<pre>",
+            "",
+            "# in pre",
+            "</pre>",
+            "~~~~",
+            "# in fence",
+            "~~~",
+            "~~~~",
+            "# Heading",
+        ];
+        let boundaries: Vec<&str> = lines
+            .iter()
+            .filter(|line| scanner.is_boundary(line))
+            .copied()
+            .collect();
+        assert_eq!(boundaries, vec!["# Heading"]);
+    }
+
+    #[test]
+    fn test_is_atx_heading() {
+        assert!(is_atx_heading("# Title"));
+        assert!(is_atx_heading("###### Six"));
+        assert!(is_atx_heading("##"));
+        assert!(!is_atx_heading("####### Seven"));
+        assert!(!is_atx_heading("#hashtag"));
+        assert!(!is_atx_heading(" # Indented"));
+    }
+
+    #[test]
+    fn test_read_error_ends_stream() {
+        let parser = MarkdownParser::new();
+        let mut stream = parser.parse_reader(Cursor::new(b"# Ok\n\n\xff\xfe".to_vec()));
+
+        assert!(matches!(stream.next(), Some(Err(ParseError::IoError(_)))));
+        assert!(stream.next().is_none());
+    }
+}
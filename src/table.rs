@@ -0,0 +1,259 @@
+//! Structured table model for table sections
+
+#[cfg(feature = "serde")]
+use crate::error::ParseError;
+
+/// Horizontal alignment of a table column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alignment {
+    /// No alignment specified (`---`)
+    #[default]
+    None,
+    /// Left aligned (`:--`)
+    Left,
+    /// Centered (`:-:`)
+    Center,
+    /// Right aligned (`--:`)
+    Right,
+}
+
+impl Alignment {
+    /// Get string representation of alignment
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Alignment::None => "none",
+            Alignment::Left => "left",
+            Alignment::Center => "center",
+            Alignment::Right => "right",
+        }
+    }
+}
+
+impl std::fmt::Display for Alignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<pulldown_cmark::Alignment> for Alignment {
+    fn from(alignment: pulldown_cmark::Alignment) -> Self {
+        match alignment {
+            pulldown_cmark::Alignment::None => Alignment::None,
+            pulldown_cmark::Alignment::Left => Alignment::Left,
+            pulldown_cmark::Alignment::Center => Alignment::Center,
+            pulldown_cmark::Alignment::Right => Alignment::Right,
+        }
+    }
+}
+
+/// A parsed table with header cells, body rows and column alignments
+///
+/// Cell text is plain text with inline formatting removed.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::MarkdownParser;
+///
+/// let parser = MarkdownParser::builder().tables(true).build();
+/// let doc = parser
+///     .parse("| Date | Version |\n|------|--------:|\n| 2025-01-01 | 0.1 |")
+///     .unwrap();
+///
+/// let table = doc.sections[0].table.as_ref().unwrap();
+/// assert_eq!(table.headers, vec!["Date", "Version"]);
+/// assert_eq!(table.column("Version"), Some(vec!["0.1"]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParsedTable {
+    /// Header cell text, one per column
+    pub headers: Vec<String>,
+    /// Body rows, each a list of cell text
+    pub rows: Vec<Vec<String>>,
+    /// Alignment of each column
+    pub alignments: Vec<Alignment>,
+}
+
+impl ParsedTable {
+    /// Get the table as plain text: one line per row, header first, with
+    /// cells separated by ` | `
+    pub(crate) fn to_text(&self) -> String {
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|row| row.join(" | "))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Get the index of the column with the given header
+    ///
+    /// Matches exactly first, then ignoring ASCII case.
+    pub fn column_index(&self, header: &str) -> Option<usize> {
+        self.headers.iter().position(|h| h == header).or_else(|| {
+            self.headers
+                .iter()
+                .position(|h| h.eq_ignore_ascii_case(header))
+        })
+    }
+
+    /// Get all body cells of the column with the given header
+    ///
+    /// Rows that are shorter than the header yield an empty string.
+    pub fn column(&self, header: &str) -> Option<Vec<&str>> {
+        let idx = self.column_index(header)?;
+        Some(
+            self.rows
+                .iter()
+                .map(|row| row.get(idx).map(String::as_str).unwrap_or(""))
+                .collect(),
+        )
+    }
+
+    /// Get a single cell by row index and header
+    pub fn cell(&self, row: usize, header: &str) -> Option<&str> {
+        let idx = self.column_index(header)?;
+        self.rows.get(row)?.get(idx).map(String::as_str)
+    }
+
+    /// Number of body rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if the table has no body rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Deserialize each body row into a user type
+    ///
+    /// Each row becomes a map from header text to cell text, so field names
+    /// must match the headers (use `#[serde(rename = "...")]` as needed) and
+    /// fields must accept strings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParseError::TableError` if a row does not match the type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use md_parser::MarkdownParser;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Change {
+    ///     #[serde(rename = "Date")]
+    ///     date: String,
+    ///     #[serde(rename = "Description")]
+    ///     description: String,
+    /// }
+    ///
+    /// let parser = MarkdownParser::builder().tables(true).build();
+    /// let doc = parser
+    ///     .parse("| Date | Description |\n|---|---|\n| 2025-01-01 | Initial draft |")
+    ///     .unwrap();
+    ///
+    /// let changes: Vec<Change> = doc.sections[0]
+    ///     .table
+    ///     .as_ref()
+    ///     .unwrap()
+    ///     .deserialize_rows()
+    ///     .unwrap();
+    /// assert_eq!(changes[0].date, "2025-01-01");
+    /// assert_eq!(changes[0].description, "Initial draft");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize_rows<T: serde::de::DeserializeOwned>(&self) -> Result<Vec<T>, ParseError> {
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let record: serde_json::Map<String, serde_json::Value> = self
+                    .headers
+                    .iter()
+                    .zip(row)
+                    .map(|(header, cell)| (header.clone(), serde_json::Value::String(cell.clone())))
+                    .collect();
+                serde_json::from_value(serde_json::Value::Object(record))
+                    .map_err(|e| ParseError::TableError(format!("Row {}: {}", i, e)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change_log() -> ParsedTable {
+        ParsedTable {
+            headers: vec!["Date".into(), "Version".into(), "Description".into()],
+            rows: vec![
+                vec!["2025-01-01".into(), "0.1".into(), "Initial draft".into()],
+                vec!["2025-02-01".into(), "0.2".into()],
+            ],
+            alignments: vec![Alignment::None, Alignment::Right, Alignment::Left],
+        }
+    }
+
+    #[test]
+    fn test_alignment_as_str() {
+        assert_eq!(Alignment::None.as_str(), "none");
+        assert_eq!(Alignment::Left.as_str(), "left");
+        assert_eq!(Alignment::Center.as_str(), "center");
+        assert_eq!(Alignment::Right.as_str(), "right");
+    }
+
+    #[test]
+    fn test_column_lookup() {
+        let table = change_log();
+
+        assert_eq!(table.column_index("Version"), Some(1));
+        assert_eq!(table.column_index("version"), Some(1));
+        assert_eq!(table.column_index("Author"), None);
+        assert_eq!(table.column("Version"), Some(vec!["0.1", "0.2"]));
+        assert_eq!(table.column("Description"), Some(vec!["Initial draft", ""]));
+    }
+
+    #[test]
+    fn test_cell_lookup() {
+        let table = change_log();
+
+        assert_eq!(table.cell(0, "Date"), Some("2025-01-01"));
+        assert_eq!(table.cell(1, "Description"), None);
+        assert_eq!(table.cell(5, "Date"), None);
+        assert_eq!(table.len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_rows() {
+        #[derive(serde::Deserialize)]
+        struct Change {
+            #[serde(rename = "Version")]
+            version: String,
+            #[serde(rename = "Description")]
+            description: Option<String>,
+        }
+
+        let changes: Vec<Change> = change_log().deserialize_rows().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1].version, "0.2");
+        assert_eq!(changes[1].description, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_rows_error() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Missing {
+            author: String,
+        }
+
+        let result: Result<Vec<Missing>, _> = change_log().deserialize_rows();
+        assert!(matches!(result, Err(ParseError::TableError(_))));
+    }
+}
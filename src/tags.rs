@@ -0,0 +1,139 @@
+//! Tags (`#tag`, `#area/sub`) and mentions (`@owner`)
+
+use std::ops::Range;
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Regex for inline tags: `#` after a boundary, then `/`-separated words
+///
+/// A `#` after a word character, `&`, `/`, `#` or `{` is part of something
+/// else (an anchor, entity, URL fragment, heading or `{#id}` attribute).
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^\w&/#{])#([\w\-]+(?:/[\w\-]+)*)").expect("Invalid tag regex")
+});
+
+/// Regex for mentions: `@` after a boundary, then a name that may contain
+/// inner `.` or `/` (`@jane.doe`, `@org/team`)
+///
+/// A `@` after a word character, `.`, `@` or `/` is part of an email
+/// address or URL.
+static MENTION_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^\w.@/])@(\w[\w\-]*(?:[./][\w\-]+)*)").expect("Invalid mention regex")
+});
+
+/// Find the tags in a section's raw text, without the `#`, in order and
+/// without duplicates
+///
+/// `base` is the byte offset of `raw` in the source; matches overlapping a
+/// `skip` range (code spans, links, inline HTML) are ignored. Purely numeric
+/// tags such as `#42` are issue references, not tags.
+pub(crate) fn tags_in<'a>(raw: &'a str, base: usize, skip: &[Range<usize>]) -> Vec<&'a str> {
+    names_in(&TAG_REGEX, raw, base, skip)
+        .filter(|tag| !tag.chars().all(|c| c.is_numeric() || c == '/'))
+        .fold(Vec::new(), push_unique)
+}
+
+/// Find the mentions in a section's raw text, without the `@`, in order and
+/// without duplicates
+pub(crate) fn mentions_in<'a>(raw: &'a str, base: usize, skip: &[Range<usize>]) -> Vec<&'a str> {
+    names_in(&MENTION_REGEX, raw, base, skip).fold(Vec::new(), push_unique)
+}
+
+/// Iterate over the names captured by `regex` outside the `skip` ranges
+fn names_in<'a, 's>(
+    regex: &'static Regex,
+    raw: &'a str,
+    base: usize,
+    skip: &'s [Range<usize>],
+) -> impl Iterator<Item = &'a str> + use<'a, 's> {
+    regex
+        .captures_iter(raw)
+        .filter_map(|caps| caps.get(1))
+        .filter(move |name| {
+            // Include the `#` or `@` before the name
+            let range = base + name.start() - 1..base + name.end();
+            !skip
+                .iter()
+                .any(|s| s.start < range.end && range.start < s.end)
+        })
+        .map(|name| name.as_str())
+}
+
+/// Append a name unless it is already present
+fn push_unique<'a>(mut names: Vec<&'a str>, name: &'a str) -> Vec<&'a str> {
+    if !names.contains(&name) {
+        names.push(name);
+    }
+    names
+}
+
+/// Read the `tags` frontmatter key, as a list or a comma/space separated
+/// string, with any leading `#` removed
+#[cfg(feature = "frontmatter")]
+pub(crate) fn frontmatter_tags(
+    frontmatter: Option<&std::collections::HashMap<String, serde_yaml::Value>>,
+) -> Vec<String> {
+    let values: Vec<&str> = match frontmatter.and_then(|fm| fm.get("tags")) {
+        Some(serde_yaml::Value::Sequence(items)) => {
+            items.iter().filter_map(serde_yaml::Value::as_str).collect()
+        }
+        Some(serde_yaml::Value::String(list)) => list
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .collect(),
+        _ => Vec::new(),
+    };
+    values
+        .into_iter()
+        .map(|tag| tag.trim().trim_start_matches('#'))
+        .filter(|tag| !tag.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_in() {
+        let raw =
+            "#urgent work on #area/sub-team, not #42 or a#b or &#39; or http://x.io/#frag #urgent";
+        assert_eq!(tags_in(raw, 0, &[]), vec!["urgent", "area/sub-team"]);
+
+        assert!(tags_in("## Heading {#custom-id}", 0, &[]).is_empty());
+
+        // `#skipped` is inside a code span at 10..20
+        let code_span = 10..20;
+        let raw = "#kept and `#skipped`";
+        assert_eq!(
+            tags_in(raw, 0, std::slice::from_ref(&code_span)),
+            vec!["kept"]
+        );
+        assert!(tags_in(raw, 100, std::slice::from_ref(&code_span)).contains(&"skipped"));
+    }
+
+    #[test]
+    fn test_mentions_in() {
+        let raw = "Ping @alice and @jane.doe. Cc @org/team, not bob@example.com, @alice!";
+        assert_eq!(
+            mentions_in(raw, 0, &[]),
+            vec!["alice", "jane.doe", "org/team"]
+        );
+    }
+
+    #[cfg(feature = "frontmatter")]
+    #[test]
+    fn test_frontmatter_tags() {
+        let parse = |yaml: &str| {
+            let fm: std::collections::HashMap<String, serde_yaml::Value> =
+                serde_yaml::from_str(yaml).unwrap();
+            frontmatter_tags(Some(&fm))
+        };
+
+        assert_eq!(parse("tags: [a, '#b/c']"), vec!["a", "b/c"]);
+        assert_eq!(parse("tags: a, b c"), vec!["a", "b", "c"]);
+        assert!(parse("title: x").is_empty());
+        assert!(frontmatter_tags(None).is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Document title sources
+
+/// Where a document title can come from
+///
+/// The parser tries its configured sources in order and records the one
+/// that produced the title in `ParsedDocument::title_source`.
+///
+/// # Example
+///
+/// ```
+/// use md_parser::{MarkdownParser, TitleSource};
+///
+/// let parser = MarkdownParser::builder()
+///     .title_sources(vec![TitleSource::FirstH1, TitleSource::FirstHeading])
+///     .build();
+/// let doc = parser.parse("## Release Notes\n\nDetails").unwrap();
+///
+/// assert_eq!(doc.title.as_deref(), Some("Release Notes"));
+/// assert_eq!(doc.title_source, Some(TitleSource::FirstHeading));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TitleSource {
+    /// A string value in the YAML frontmatter (requires the `frontmatter` feature)
+    Frontmatter(String),
+    /// The first level-1 heading (ATX `#` or setext `===`)
+    FirstH1,
+    /// The first heading of any level
+    FirstHeading,
+    /// The file name without extension (only used by `parse_file`)
+    Filename,
+}
+
+impl TitleSource {
+    /// The `title` frontmatter key
+    pub fn frontmatter_title() -> Self {
+        TitleSource::Frontmatter("title".to_string())
+    }
+
+    /// Default sources: the first H1 only
+    pub fn defaults() -> Vec<Self> {
+        vec![TitleSource::FirstH1]
+    }
+
+    /// Parse a source name: `h1`, `heading`, `filename` or `frontmatter:<key>`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "h1" => Some(TitleSource::FirstH1),
+            "heading" => Some(TitleSource::FirstHeading),
+            "filename" => Some(TitleSource::Filename),
+            "frontmatter" => Some(TitleSource::frontmatter_title()),
+            _ => name
+                .strip_prefix("frontmatter:")
+                .filter(|key| !key.is_empty())
+                .map(|key| TitleSource::Frontmatter(key.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for TitleSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TitleSource::Frontmatter(key) => write!(f, "frontmatter:{}", key),
+            TitleSource::FirstH1 => write!(f, "h1"),
+            TitleSource::FirstHeading => write!(f, "heading"),
+            TitleSource::Filename => write!(f, "filename"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name_round_trip() {
+        for name in ["h1", "heading", "filename", "frontmatter:name"] {
+            assert_eq!(TitleSource::from_name(name).unwrap().to_string(), name);
+        }
+        assert_eq!(
+            TitleSource::from_name("frontmatter"),
+            Some(TitleSource::frontmatter_title())
+        );
+        assert_eq!(TitleSource::from_name("frontmatter:"), None);
+        assert_eq!(TitleSource::from_name("h2"), None);
+    }
+}
@@ -4,10 +4,22 @@ use regex::Regex;
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
+use crate::span::{LineIndex, SourceSpan};
+
 /// Regex for matching template variables: `{{variable_name}}`
 static VARIABLE_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\{\{(\w+)\}\}").expect("Invalid variable regex"));
 
+/// A single occurrence of a template variable in the source text
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableOccurrence {
+    /// Variable name (without the braces)
+    pub name: String,
+    /// Location of the full `{{name}}` pattern
+    pub span: SourceSpan,
+}
+
 /// Extract all variable names from content
 ///
 /// Finds all `{{variable_name}}` patterns and returns the variable names.
@@ -23,11 +35,15 @@ static VARIABLE_REGEX: LazyLock<Regex> =
 /// assert_eq!(vars, vec!["name", "order_id", "name"]);
 /// ```
 pub fn extract_variables(content: &str) -> Vec<String> {
+    variable_names(content).map(String::from).collect()
+}
+
+/// Iterate over the variable names in content, borrowing from it
+pub(crate) fn variable_names(content: &str) -> impl Iterator<Item = &str> {
     VARIABLE_REGEX
         .captures_iter(content)
         .filter_map(|cap| cap.get(1))
-        .map(|m| m.as_str().to_string())
-        .collect()
+        .map(|m| m.as_str())
 }
 
 /// Extract unique variable names from content
@@ -55,6 +71,42 @@ pub fn extract_unique_variables(content: &str) -> Vec<String> {
     vars
 }
 
+/// Extract every variable occurrence with its source location
+///
+/// # Example
+///
+/// ```
+/// use md_parser::extract_variable_occurrences;
+///
+/// let occurrences = extract_variable_occurrences("Hi {{name}}!\nBye {{name}}.");
+/// assert_eq!(occurrences.len(), 2);
+/// assert_eq!(occurrences[1].span.byte_range, 17..25);
+/// assert_eq!(occurrences[1].span.start_line, 2);
+/// assert_eq!(occurrences[1].span.start_col, 5);
+/// ```
+pub fn extract_variable_occurrences(content: &str) -> Vec<VariableOccurrence> {
+    variable_occurrences_in(content, 0, &LineIndex::new(content))
+}
+
+/// Extract variable occurrences from a slice starting at `base` in the indexed source
+pub(crate) fn variable_occurrences_in(
+    content: &str,
+    base: usize,
+    lines: &LineIndex<'_>,
+) -> Vec<VariableOccurrence> {
+    VARIABLE_REGEX
+        .captures_iter(content)
+        .filter_map(|cap| {
+            let full = cap.get(0)?;
+            let name = cap.get(1)?;
+            Some(VariableOccurrence {
+                name: name.as_str().to_string(),
+                span: lines.span(base + full.start()..base + full.end()),
+            })
+        })
+        .collect()
+}
+
 /// Check if content contains any template variables
 ///
 /// # Example
@@ -173,4 +225,17 @@ mod tests {
         let vars = extract_variables(content);
         assert_eq!(vars, vec!["a", "b", "c"]);
     }
+
+    #[test]
+    fn test_variable_occurrences_with_base() {
+        let source = "# T\n\nA {{x}}";
+        let lines = LineIndex::new(source);
+        let occurrences = variable_occurrences_in(&source[5..], 5, &lines);
+
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].name, "x");
+        assert_eq!(occurrences[0].span.byte_range, 7..12);
+        assert_eq!(occurrences[0].span.start_line, 3);
+        assert_eq!(occurrences[0].span.start_col, 3);
+    }
 }
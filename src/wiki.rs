@@ -0,0 +1,150 @@
+//! Wiki-links and embeds (`[[Page]]`, `![[Page]]`)
+
+use std::ops::Range;
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::section::{SectionType, SectionView};
+use crate::span::{LineIndex, SourceSpan};
+
+/// Regex for wiki-link syntax: `[[target#heading|alias]]`, optionally
+/// preceded by `!` for embeds
+static WIKI_LINK_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(!?)\[\[([^\[\]|#\n]*)(?:#([^\[\]|\n]*))?(?:\|([^\[\]\n]*))?\]\]")
+        .expect("Invalid wiki-link regex")
+});
+
+/// A wiki-link or embed in Obsidian syntax
+///
+/// # Example
+///
+/// ```
+/// use md_parser::MarkdownParser;
+///
+/// let doc = MarkdownParser::new()
+///     .parse("# Notes\n\nSee [[Design Doc#Goals|the goals]] and [[#Notes]].\n\n![[diagram.png]]")
+///     .unwrap();
+///
+/// let link = &doc.wiki_links[0];
+/// assert_eq!(link.target, "Design Doc");
+/// assert_eq!(link.heading.as_deref(), Some("Goals"));
+/// assert_eq!(link.alias.as_deref(), Some("the goals"));
+/// assert_eq!(link.section_idx, Some(1));
+///
+/// assert_eq!(doc.wiki_links[1].target, "");
+/// assert!(doc.wiki_links[2].embed);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WikiLink {
+    /// Linked note or file (empty for links within the same note)
+    pub target: String,
+    /// Heading anchor after `#`
+    pub heading: Option<String>,
+    /// Block reference after `#^`
+    pub block: Option<String>,
+    /// Display text after `|`
+    pub alias: Option<String>,
+    /// Whether this is an embed (`![[...]]`)
+    pub embed: bool,
+    /// Index of the section containing the link
+    pub section_idx: Option<usize>,
+    /// Location of the full `[[...]]` syntax, including any `!`
+    pub span: SourceSpan,
+}
+
+/// Find the wiki-links in every section outside code sections and the
+/// `skip` ranges (code spans, nested code blocks and inline HTML)
+pub(crate) fn wiki_links_in<S: SectionView>(
+    sections: &[S],
+    skip: &[Range<usize>],
+    lines: &LineIndex<'_>,
+) -> Vec<WikiLink> {
+    let mut links = Vec::new();
+
+    for (idx, section) in sections.iter().enumerate() {
+        if section.section_type() == SectionType::Code {
+            continue;
+        }
+        let base = section.span().byte_range.start;
+        for caps in WIKI_LINK_REGEX.captures_iter(section.raw()) {
+            let Some(full) = caps.get(0) else {
+                continue;
+            };
+            let range = base + full.start()..base + full.end();
+            let skipped = skip
+                .iter()
+                .any(|s| s.start < range.end && range.start < s.end);
+            let target = caps.get(2).map_or("", |m| m.as_str().trim());
+            let anchor = caps.get(3).map(|m| m.as_str().trim());
+            if skipped || (target.is_empty() && anchor.is_none_or(str::is_empty)) {
+                continue;
+            }
+
+            let (heading, block) = match anchor {
+                Some(anchor) => match anchor.strip_prefix('^') {
+                    Some(block) => (None, Some(block.to_string())),
+                    None => (Some(anchor.to_string()), None),
+                },
+                None => (None, None),
+            };
+            links.push(WikiLink {
+                target: target.to_string(),
+                heading,
+                block,
+                alias: caps.get(4).map(|m| m.as_str().trim().to_string()),
+                embed: caps.get(1).is_some_and(|m| !m.as_str().is_empty()),
+                section_idx: Some(idx),
+                span: lines.span(range),
+            });
+        }
+    }
+
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::section::ParsedSection;
+
+    fn links(source: &str, skip: &[Range<usize>]) -> Vec<WikiLink> {
+        let lines = LineIndex::new(source);
+        let section = ParsedSection::new(SectionType::Paragraph, String::new(), 0)
+            .with_raw(source.to_string())
+            .with_span(lines.span(0..source.len()));
+        wiki_links_in(&[section], skip, &lines)
+    }
+
+    #[test]
+    fn test_wiki_link_parts() {
+        let found = links(
+            "[[Page#^abc123]] ![[Page#Intro|Shown]] [[ Spaced | Alias ]]",
+            &[],
+        );
+
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].target, "Page");
+        assert_eq!(found[0].block.as_deref(), Some("abc123"));
+        assert_eq!(found[0].heading, None);
+        assert!(found[1].embed);
+        assert_eq!(found[1].heading.as_deref(), Some("Intro"));
+        assert_eq!(found[1].alias.as_deref(), Some("Shown"));
+        assert_eq!(found[1].span.byte_range, 17..38);
+        assert_eq!(found[2].target, "Spaced");
+        assert_eq!(found[2].alias.as_deref(), Some("Alias"));
+    }
+
+    #[test]
+    fn test_wiki_links_skip_code_and_empty() {
+        let code_span = 11..21;
+        let found = links(
+            "[[]] [[#]] `[[Code]]` [[Real]]",
+            std::slice::from_ref(&code_span),
+        );
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].target, "Real");
+    }
+}
@@ -1,6 +1,7 @@
 //! Integration tests for md-parser
 
 use md_parser::{extract_checklist_items, ChecklistSummary, MarkdownParser, SectionType};
+use md_parser::{FieldValue, IdStrategy, ParserOptions, TitleSource};
 use std::path::PathBuf;
 
 fn fixture_path(name: &str) -> PathBuf {
@@ -55,6 +56,24 @@ fn test_parse_checklist_document() {
     assert_eq!(task2.ac_refs, vec!["2", "3"]);
 }
 
+#[test]
+fn test_checklist_sections() {
+    let parser = MarkdownParser::new();
+    let doc = parser
+        .parse_file(&fixture_path("with_checklist.md"))
+        .unwrap();
+
+    let checklists = doc.sections_by_type(SectionType::Checklist);
+    assert_eq!(checklists.len(), 2);
+    assert!(doc.sections_by_type(SectionType::List).is_empty());
+
+    // Every item, nested or not, points at its checklist section
+    assert_eq!(doc.sections[2].section_type, SectionType::Checklist);
+    assert_eq!(doc.checklist_items_in(2).len(), 4);
+    assert_eq!(doc.checklist_items[1].section_idx, Some(2));
+    assert_eq!(doc.checklist_items[5].section_idx, Some(4));
+}
+
 #[test]
 fn test_parse_variables_document() {
     let parser = MarkdownParser::new();
@@ -107,6 +126,54 @@ fn test_parse_frontmatter_document() {
     assert!(doc.variables.contains(&"author".to_string()));
 }
 
+#[cfg(feature = "frontmatter")]
+#[test]
+fn test_tags_merge_frontmatter() {
+    let parser = MarkdownParser::new();
+    let doc = parser
+        .parse_file(&fixture_path("with_frontmatter.md"))
+        .unwrap();
+
+    assert_eq!(doc.sections[1].tags, vec!["draft", "rust"]);
+    assert_eq!(doc.sections[1].mentions, vec!["core"]);
+    assert_eq!(
+        doc.tags.iter().map(String::as_str).collect::<Vec<_>>(),
+        vec!["draft", "markdown", "parser", "rust"]
+    );
+    assert!(doc.mentions.contains("core"));
+}
+
+#[cfg(feature = "frontmatter")]
+#[test]
+fn test_frontmatter_title_source() {
+    let parser = MarkdownParser::builder()
+        .title_sources(vec![TitleSource::frontmatter_title(), TitleSource::FirstH1])
+        .build();
+    let doc = parser
+        .parse_file(&fixture_path("with_frontmatter.md"))
+        .unwrap();
+
+    assert_eq!(doc.title.as_deref(), Some("Test Document"));
+    assert_eq!(doc.title_source, Some(TitleSource::frontmatter_title()));
+
+    // Without the frontmatter source the H1 template is used
+    let doc = MarkdownParser::new()
+        .parse_file(&fixture_path("with_frontmatter.md"))
+        .unwrap();
+    assert_eq!(doc.title.as_deref(), Some("{{title}}"));
+}
+
+#[test]
+fn test_filename_title_source() {
+    let parser = MarkdownParser::builder()
+        .title_sources(vec![TitleSource::Filename])
+        .build();
+    let doc = parser.parse_file(&fixture_path("simple.md")).unwrap();
+
+    assert_eq!(doc.title.as_deref(), Some("simple"));
+    assert_eq!(doc.title_source, Some(TitleSource::Filename));
+}
+
 #[test]
 fn test_parse_malformed_document() {
     let parser = MarkdownParser::new();
@@ -152,6 +219,61 @@ fn test_parse_bmad_story() {
     assert!(doc.variables.contains(&"template_name".to_string()));
 }
 
+#[test]
+fn test_parse_bmad_story_gfm_table() {
+    let parser = MarkdownParser::with_options(ParserOptions::gfm());
+    let doc = parser.parse_file(&fixture_path("bmad_story.md")).unwrap();
+
+    let tables = doc.sections_by_type(SectionType::Table);
+    assert_eq!(tables.len(), 1);
+    assert!(tables[0].content.contains("Initial draft"));
+
+    let table = tables[0].table.as_ref().unwrap();
+    assert_eq!(table.headers, vec!["Date", "Version", "Description"]);
+    assert_eq!(table.column("Description"), Some(vec!["Initial draft"]));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_bmad_change_log_records() {
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Change {
+        date: String,
+        version: String,
+        description: String,
+    }
+
+    let parser = MarkdownParser::with_options(ParserOptions::gfm());
+    let doc = parser.parse_file(&fixture_path("bmad_story.md")).unwrap();
+
+    let table = doc.sections_by_type(SectionType::Table)[0]
+        .table
+        .as_ref()
+        .unwrap();
+    let changes: Vec<Change> = table.deserialize_rows().unwrap();
+
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].date, "2025-01-01");
+    assert_eq!(changes[0].version, "0.1");
+    assert_eq!(changes[0].description, "Initial draft");
+}
+
+#[test]
+fn test_bmad_heading_anchors() {
+    let parser = MarkdownParser::new();
+    let doc = parser.parse_file(&fixture_path("bmad_story.md")).unwrap();
+
+    assert_eq!(
+        doc.sections[0].anchor.as_deref(),
+        Some("story-tea-test-001-example-story")
+    );
+    let tasks = doc.section_by_anchor("#tasks--subtasks").unwrap();
+    assert_eq!(tasks.content, "Tasks / Subtasks");
+    assert!(doc.section_by_anchor("phase-1-setup").is_some());
+    assert!(doc.section_by_anchor("missing").is_none());
+}
+
 #[test]
 fn test_standalone_checklist_extraction() {
     let content = std::fs::read_to_string(fixture_path("with_checklist.md")).unwrap();
@@ -169,16 +291,42 @@ fn test_edges_generated() {
     let parser = MarkdownParser::new();
     let doc = parser.parse_file(&fixture_path("simple.md")).unwrap();
 
-    // Should have n-1 edges for n sections
-    assert_eq!(doc.edges.len(), doc.sections.len() - 1);
+    let follows: Vec<_> = doc
+        .edges
+        .iter()
+        .filter(|e| e.edge_type == md_parser::EdgeType::Follows)
+        .collect();
 
-    // All edges should be "follows" type
-    for edge in &doc.edges {
-        assert_eq!(edge.edge_type, md_parser::EdgeType::Follows);
+    // Should have n-1 follows edges for n sections
+    assert_eq!(follows.len(), doc.sections.len() - 1);
+    for edge in follows {
         assert_eq!(edge.target_idx, edge.source_idx + 1);
     }
 }
 
+#[test]
+fn test_heading_hierarchy() {
+    let parser = MarkdownParser::new();
+    let doc = parser.parse_file(&fixture_path("bmad_story.md")).unwrap();
+
+    // Every section after the H1 is nested under it
+    assert_eq!(doc.children_of(0).len(), 6);
+
+    let task = doc
+        .sections
+        .iter()
+        .position(|s| s.content.contains("Implement parser"))
+        .unwrap();
+    assert_eq!(
+        doc.heading_path(task),
+        vec![
+            "Story TEA-TEST-001: Example Story",
+            "Tasks / Subtasks",
+            "Phase 2: Implementation"
+        ]
+    );
+}
+
 #[test]
 fn test_section_ids_unique() {
     let parser = MarkdownParser::new();
@@ -190,6 +338,22 @@ fn test_section_ids_unique() {
     assert_eq!(ids.len(), unique_count, "All section IDs should be unique");
 }
 
+#[test]
+fn test_section_ids_stable_across_parses() {
+    for strategy in [IdStrategy::ContentHash, IdStrategy::HeadingSlug] {
+        let parser = MarkdownParser::builder().id_strategy(strategy).build();
+        let first = parser.parse_file(&fixture_path("bmad_story.md")).unwrap();
+        let second = parser.parse_file(&fixture_path("bmad_story.md")).unwrap();
+
+        let first_ids: Vec<_> = first.sections.iter().map(|s| &s.id).collect();
+        let second_ids: Vec<_> = second.sections.iter().map(|s| &s.id).collect();
+        assert_eq!(first_ids, second_ids);
+
+        let unique: std::collections::HashSet<_> = first_ids.iter().collect();
+        assert_eq!(unique.len(), first_ids.len());
+    }
+}
+
 #[test]
 fn test_order_idx_sequential() {
     let parser = MarkdownParser::new();
@@ -220,3 +384,163 @@ fn test_json_serialization() {
     assert_eq!(deserialized.title, doc.title);
     assert_eq!(deserialized.sections.len(), doc.sections.len());
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_callout_serialization() {
+    let parser = MarkdownParser::new();
+    let doc = parser
+        .parse("> [!WARNING] Heads up\n> Do not deploy on Fridays.")
+        .unwrap();
+
+    let json = serde_json::to_value(&doc.sections[0]).unwrap();
+    assert_eq!(json["callout"]["kind"], "Warning");
+    assert_eq!(json["callout"]["title"], "Heads up");
+    assert_eq!(json["callout"]["content"], "Do not deploy on Fridays.");
+}
+
+#[test]
+fn test_stream_matches_parse() {
+    let parser = MarkdownParser::builder()
+        .id_strategy(IdStrategy::HeadingSlug)
+        .build();
+    for name in ["bmad_story.md", "with_frontmatter.md", "malformed.md"] {
+        let path = fixture_path(name);
+        let doc = parser.parse_file(&path).unwrap();
+        let file = std::io::BufReader::new(std::fs::File::open(&path).unwrap());
+        let streamed: Vec<_> = parser.parse_reader(file).collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(streamed.len(), doc.sections.len(), "{}", name);
+        for (streamed, section) in streamed.iter().zip(&doc.sections) {
+            assert_eq!(streamed.section.id, section.id);
+            assert_eq!(streamed.section.content, section.content);
+            assert_eq!(streamed.section.span, section.span);
+            assert_eq!(streamed.section.variables, section.variables);
+            assert_eq!(streamed.section.tags, section.tags);
+        }
+
+        let items: usize = streamed.iter().map(|s| s.checklist_items.len()).sum();
+        assert_eq!(items, doc.checklist_items.len(), "{}", name);
+    }
+}
+
+#[test]
+fn test_parse_dir_and_many() {
+    let parser = MarkdownParser::builder()
+        .id_strategy(IdStrategy::ContentHash)
+        .build();
+    let root = fixture_path("");
+    let results = parser.parse_dir(&root, "*.md").unwrap();
+
+    assert_eq!(results.len(), 9);
+    let simple = results[&fixture_path("simple.md")].as_ref().unwrap();
+    let single = parser.parse_file(&fixture_path("simple.md")).unwrap();
+    assert_eq!(simple.title, single.title);
+    assert_eq!(simple.sections[1].id, single.sections[1].id);
+
+    assert!(parser.parse_dir(&root, "with_*.txt").unwrap().is_empty());
+    assert!(matches!(
+        parser.parse_dir(&root, "***.md"),
+        Err(md_parser::ParseError::InvalidPattern(_))
+    ));
+
+    let missing = fixture_path("missing.md");
+    let results = parser.parse_many([fixture_path("simple.md"), missing.clone()]);
+    assert!(results[&fixture_path("simple.md")].is_ok());
+    assert!(matches!(
+        results[&missing],
+        Err(md_parser::ParseError::IoError(_))
+    ));
+}
+
+#[test]
+fn test_parse_latin1_file() {
+    let parser = MarkdownParser::builder()
+        .normalize_line_endings(true)
+        .build();
+    let result = parser.parse_file(&fixture_path("latin1.md"));
+
+    #[cfg(feature = "encoding")]
+    {
+        let doc = result.unwrap();
+        assert_eq!(doc.encoding.as_deref(), Some("windows-1252"));
+        assert_eq!(doc.title.as_deref(), Some("Café Notes"));
+        assert_eq!(doc.checklist_items[0].text, "Résumé sent");
+        assert!(doc.sections.iter().all(|s| !s.raw.contains('\r')));
+    }
+    #[cfg(not(feature = "encoding"))]
+    assert!(matches!(
+        result,
+        Err(md_parser::ParseError::InvalidEncoding(_))
+    ));
+}
+
+#[test]
+fn test_wiki_links() {
+    let parser = MarkdownParser::new();
+    let doc = parser.parse_file(&fixture_path("wiki_note.md")).unwrap();
+
+    let targets: Vec<_> = doc.wiki_links.iter().map(|l| l.target.as_str()).collect();
+    assert_eq!(
+        targets,
+        vec![
+            "Project Beta",
+            "Roadmap",
+            "Roadmap",
+            "architecture.png",
+            "",
+            "Postgres"
+        ]
+    );
+
+    let goals = &doc.wiki_links[1];
+    assert_eq!(goals.heading.as_deref(), Some("Q3 Goals"));
+    assert_eq!(goals.alias.as_deref(), Some("Q3 goals"));
+    assert_eq!(doc.wiki_links[2].block.as_deref(), Some("milestone-1"));
+    assert!(doc.wiki_links[3].embed);
+    assert_eq!(doc.wiki_links[4].heading.as_deref(), Some("Decisions"));
+
+    // Each link points at the section it appears in
+    for link in &doc.wiki_links {
+        let section = &doc.sections[link.section_idx.unwrap()];
+        assert!(section.span.contains(link.span.byte_range.start));
+    }
+    assert_eq!(doc.wiki_links[5].span.start_line, 17);
+}
+
+#[test]
+fn test_inline_fields() {
+    let path = fixture_path("inline_fields.md");
+    let doc = MarkdownParser::new().parse_file(&path).unwrap();
+
+    assert_eq!(doc.sections[1].fields["Status"].as_str(), Some("Approved"));
+    assert_eq!(doc.sections[1].fields["Estimate"].as_str(), Some("5"));
+    assert_eq!(doc.sections[3].fields.len(), 3);
+    assert_eq!(doc.fields["Owner"].as_str(), Some("@dana"));
+    assert_eq!(doc.fields["review"].as_str(), Some("pending"));
+    assert!(!doc.fields.contains_key("format"));
+    assert!(!doc.fields.contains_key("status"));
+    assert!(!doc.fields.contains_key("nested"));
+    assert!(!doc.fields.keys().any(|key| key.contains("std")));
+    assert_eq!(doc.fields.len(), 8);
+
+    let doc = MarkdownParser::builder()
+        .coerce_fields(true)
+        .build()
+        .parse_file(&path)
+        .unwrap();
+    assert_eq!(doc.fields["Estimate"], FieldValue::Number(5.0));
+    assert_eq!(doc.fields["Blocked"], FieldValue::Bool(false));
+    assert_eq!(
+        doc.fields["due"],
+        FieldValue::Date("2024-06-30".to_string())
+    );
+    assert_eq!(doc.fields["sprint"].as_f64(), Some(14.0));
+    assert_eq!(
+        doc.fields["labels"],
+        FieldValue::List(vec![
+            FieldValue::Text("export".to_string()),
+            FieldValue::Text("reports".to_string()),
+        ])
+    );
+}